@@ -0,0 +1,93 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+// 세대마다 새 세마포어(gate)를 만들어 쓰므로, 다음 세대의 스레드가 이전 세대의 permit을 가로챌 수 없다.
+struct BarrierState {
+    arrived: usize,
+    generation: usize,
+    gate: Arc<CountingSemaphore>,
+}
+
+pub struct Barrier {
+    parties: usize,
+    state: Mutex<BarrierState>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    pub generation: usize,
+    pub is_leader: bool,
+}
+
+impl Barrier {
+    pub fn new(parties: usize) -> Self {
+        assert!(parties > 0, "a barrier needs at least one party");
+        Self {
+            parties,
+            state: Mutex::new(BarrierState {
+                arrived: 0,
+                generation: 0,
+                gate: Arc::new(CountingSemaphore::untracked(0)),
+            }),
+        }
+    }
+
+    pub fn parties(&self) -> usize {
+        self.parties
+    }
+
+    pub fn generation(&self) -> usize {
+        self.state.lock().unwrap().generation
+    }
+
+    pub fn wait(&self) -> BarrierWaitResult {
+        match self.arrive() {
+            Ok(result) => result,
+            Err((generation, gate)) => {
+                gate.acquire();
+                BarrierWaitResult { generation, is_leader: false }
+            }
+        }
+    }
+
+    // 타임아웃이 나면 도착을 철회하므로 barrier는 계속 재사용할 수 있다.
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<BarrierWaitResult, AcquireError> {
+        let (generation, gate) = match self.arrive() {
+            Ok(result) => return Ok(result),
+            Err(waiting) => waiting,
+        };
+
+        if gate.acquire_timeout(timeout).is_ok() {
+            return Ok(BarrierWaitResult { generation, is_leader: false });
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation {
+            state.arrived -= 1;
+            return Err(AcquireError::Timeout);
+        }
+        drop(state);
+
+        // 타임아웃 직후 barrier가 열렸다면 이미 이 스레드 몫의 permit이 풀려 있다.
+        gate.acquire();
+        Ok(BarrierWaitResult { generation, is_leader: false })
+    }
+
+    fn arrive(&self) -> Result<BarrierWaitResult, (usize, Arc<CountingSemaphore>)> {
+        let mut state = self.state.lock().unwrap();
+        let generation = state.generation;
+        state.arrived += 1;
+
+        if state.arrived < self.parties {
+            return Err((generation, Arc::clone(&state.gate)));
+        }
+
+        state.gate.release_many(self.parties - 1);
+        state.gate = Arc::new(CountingSemaphore::untracked(0));
+        state.arrived = 0;
+        state.generation += 1;
+        Ok(BarrierWaitResult { generation, is_leader: true })
+    }
+}
@@ -1,31 +1,121 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use super::error::AcquireError;
+use super::ownership::OwnershipTracker;
+use super::watchdog::{HoldSlot, Watchdog};
 
 pub struct BinarySemaphore {
     flag: Mutex<bool>,
     cond: Condvar,
+    // flag lock 아래에서만 바뀐다.
+    waiting: AtomicUsize,
+    tracker: OwnershipTracker,
+    holds: HoldSlot,
 }
 
 impl BinarySemaphore {
     pub fn new() -> Self {
+        Self::with_tracker(OwnershipTracker::new("BinarySemaphore"))
+    }
+
+    pub(crate) fn untracked() -> Self {
+        Self::with_tracker(OwnershipTracker::disabled())
+    }
+
+    fn with_tracker(tracker: OwnershipTracker) -> Self {
         Self {
             flag: Mutex::new(true),
             cond: Condvar::new(),
+            waiting: AtomicUsize::new(0),
+            tracker,
+            holds: HoldSlot::default(),
         }
     }
 
     pub fn acquire(&self) {
         let mut flag = self.flag.lock().unwrap();
 
-        while !*flag {
-            flag = self.cond.wait(flag).unwrap();
+        if !*flag {
+            self.waiting.fetch_add(1, Ordering::SeqCst);
+            while !*flag {
+                flag = self.cond.wait(flag).unwrap();
+            }
+            self.waiting.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        *flag = false;
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        let mut flag = self.flag.lock().unwrap();
+        if !*flag {
+            return Err(AcquireError::WouldBlock);
         }
+        *flag = false;
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+        Ok(())
+    }
 
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        let deadline = Instant::now() + timeout;
+        let mut flag = self.flag.lock().unwrap();
+        if !*flag {
+            self.waiting.fetch_add(1, Ordering::SeqCst);
+            while !*flag {
+                let now = Instant::now();
+                if now >= deadline {
+                    self.waiting.fetch_sub(1, Ordering::SeqCst);
+                    return Err(AcquireError::Timeout);
+                }
+                flag = self.cond.wait_timeout(flag, deadline - now).unwrap().0;
+            }
+            self.waiting.fetch_sub(1, Ordering::SeqCst);
+        }
         *flag = false;
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+        Ok(())
+    }
+
+    pub fn available_permits(&self) -> usize {
+        *self.flag.lock().unwrap() as usize
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        self.waiting.load(Ordering::SeqCst)
+    }
+
+    pub fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.holds.watch(watchdog, name)
     }
 
     pub fn release(&self) {
+        self.holds.on_release(1);
         let mut flag = self.flag.lock().unwrap();
         *flag = true;
         self.cond.notify_one();
+        drop(flag);
+        self.tracker.on_release(1);
     }
-}
\ No newline at end of file
+}
+
+impl Default for BinarySemaphore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for BinarySemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = self.flag.lock().unwrap();
+        f.debug_struct("BinarySemaphore")
+            .field("available_permits", &(*flag as usize))
+            .field("waiting_threads", &self.waiting.load(Ordering::SeqCst))
+            .finish()
+    }
+}
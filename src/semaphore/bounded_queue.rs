@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use super::counting_semaphore::CountingSemaphore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    Full(T),
+    Disconnected(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendTimeoutError<T> {
+    Timeout(T),
+    Disconnected(T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,
+    Disconnected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Disconnected,
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sending on a closed queue")
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "receiving on an empty and closed queue")
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+impl std::error::Error for RecvError {}
+
+// empty_slots와 filled_slots 두 세마포어로 용량을 관리하고, buffer 잠금은 push/pop 순간에만 잡는다.
+// close 시에는 각 세마포어에 permit을 하나씩 더 풀어 두고, 그 permit을 받은 스레드가
+// 닫힌 것을 확인한 뒤 다시 풀어 주는 식으로 대기 중인 모든 스레드를 차례로 깨운다.
+pub struct BoundedQueue<T> {
+    capacity: usize,
+    buffer: Mutex<VecDeque<T>>,
+    empty_slots: CountingSemaphore,
+    filled_slots: CountingSemaphore,
+    closed: AtomicBool,
+}
+
+impl<T> BoundedQueue<T> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        Self {
+            capacity,
+            buffer: Mutex::new(VecDeque::with_capacity(capacity)),
+            empty_slots: CountingSemaphore::untracked(capacity),
+            filled_slots: CountingSemaphore::untracked(0),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn channel(capacity: usize) -> (Sender<T>, Receiver<T>) {
+        let shared = Arc::new(Shared {
+            queue: BoundedQueue::new(capacity),
+            senders: AtomicUsize::new(1),
+            receivers: AtomicUsize::new(1),
+        });
+        (Sender { shared: Arc::clone(&shared) }, Receiver { shared })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    pub fn close(&self) {
+        // buffer 잠금 아래에서 닫아야 push 도중인 송신자와 경합하지 않는다.
+        let _buffer = self.buffer.lock().unwrap();
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.empty_slots.release();
+        self.filled_slots.release();
+    }
+
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.empty_slots.acquire();
+        self.push(value).map_err(SendError)
+    }
+
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        if self.is_closed() {
+            return Err(TrySendError::Disconnected(value));
+        }
+        match self.empty_slots.try_acquire() {
+            Ok(()) => self.push(value).map_err(TrySendError::Disconnected),
+            Err(_) => Err(TrySendError::Full(value)),
+        }
+    }
+
+    pub fn send_timeout(&self, value: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        match self.empty_slots.acquire_timeout(timeout) {
+            Ok(()) => self.push(value).map_err(SendTimeoutError::Disconnected),
+            Err(_) => Err(SendTimeoutError::Timeout(value)),
+        }
+    }
+
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.filled_slots.acquire();
+        self.pop().ok_or(RecvError)
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match self.filled_slots.try_acquire() {
+            Ok(()) => self.pop().ok_or(TryRecvError::Disconnected),
+            Err(_) => Err(TryRecvError::Empty),
+        }
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match self.filled_slots.acquire_timeout(timeout) {
+            Ok(()) => self.pop().ok_or(RecvTimeoutError::Disconnected),
+            Err(_) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    // empty_slots permit을 이미 얻은 상태에서 호출된다.
+    fn push(&self, value: T) -> Result<(), T> {
+        let mut buffer = self.buffer.lock().unwrap();
+        if self.is_closed() {
+            drop(buffer);
+            self.empty_slots.release();
+            return Err(value);
+        }
+        buffer.push_back(value);
+        drop(buffer);
+        self.filled_slots.release();
+        Ok(())
+    }
+
+    // filled_slots permit을 이미 얻은 상태에서 호출된다. 닫힌 뒤 버퍼가 비었으면 None.
+    fn pop(&self) -> Option<T> {
+        let mut buffer = self.buffer.lock().unwrap();
+        match buffer.pop_front() {
+            Some(value) => {
+                drop(buffer);
+                self.empty_slots.release();
+                Some(value)
+            }
+            None => {
+                drop(buffer);
+                self.filled_slots.release();
+                None
+            }
+        }
+    }
+}
+
+struct Shared<T> {
+    queue: BoundedQueue<T>,
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+}
+
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        self.shared.queue.send(value)
+    }
+
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        self.shared.queue.try_send(value)
+    }
+
+    pub fn send_timeout(&self, value: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        self.shared.queue.send_timeout(value, timeout)
+    }
+
+    pub fn close(&self) {
+        self.shared.queue.close();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.queue.is_closed()
+    }
+}
+
+impl<T> Receiver<T> {
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.shared.queue.recv()
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.shared.queue.try_recv()
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        self.shared.queue.recv_timeout(timeout)
+    }
+
+    pub fn close(&self) {
+        self.shared.queue.close();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.queue.is_closed()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.recv().ok())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::SeqCst);
+        Self { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.receivers.fetch_add(1, Ordering::SeqCst);
+        Self { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.queue.close();
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if self.shared.receivers.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.queue.close();
+        }
+    }
+}
@@ -0,0 +1,541 @@
+use std::fmt;
+use std::hint;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use super::counting_semaphore::CountingSemaphore;
+use super::drain::DrainPolicy;
+use super::error::{AcquireError, ReleaseError};
+use super::ownership::{Misuse, OwnershipTracker};
+use super::priority_semaphore::PrioritySemaphore;
+use super::strong_semaphore::StrongSemaphore;
+use super::weak_semaphore::WeakSemaphore;
+
+// 설정에 따라 런타임에 세마포어 종류를 고를 수 있도록 하는 공통 인터페이스.
+pub trait Semaphore: Send + Sync {
+    fn acquire(&self);
+    fn try_acquire(&self) -> Result<(), AcquireError>;
+    fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError>;
+    fn release(&self);
+    fn available_permits(&self) -> usize;
+    fn waiting_threads(&self) -> usize;
+    fn drain(&self);
+    fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError>;
+    fn reopen(&self);
+    fn is_draining(&self) -> bool;
+    fn forget_permits(&self, n: usize) -> usize;
+    fn forget_held_permit(&self);
+    fn add_permits(&self, n: usize);
+
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    fn metrics(&self) -> Option<SemaphoreMetrics> {
+        None
+    }
+}
+
+macro_rules! impl_semaphore {
+    ($($ty:ty),*) => {$(
+        impl Semaphore for $ty {
+            fn acquire(&self) {
+                <$ty>::acquire(self)
+            }
+            fn try_acquire(&self) -> Result<(), AcquireError> {
+                <$ty>::try_acquire(self)
+            }
+            fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+                <$ty>::acquire_timeout(self, timeout)
+            }
+            fn release(&self) {
+                <$ty>::release(self)
+            }
+            fn available_permits(&self) -> usize {
+                <$ty>::available_permits(self)
+            }
+            fn waiting_threads(&self) -> usize {
+                <$ty>::waiting_threads(self)
+            }
+            fn drain(&self) {
+                <$ty>::drain(self)
+            }
+            fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+                <$ty>::drain_timeout(self, timeout)
+            }
+            fn reopen(&self) {
+                <$ty>::reopen(self)
+            }
+            fn is_draining(&self) -> bool {
+                <$ty>::is_draining(self)
+            }
+            fn forget_permits(&self, n: usize) -> usize {
+                <$ty>::forget_permits(self, n)
+            }
+            fn forget_held_permit(&self) {
+                <$ty>::forget_held_permit(self)
+            }
+            fn add_permits(&self, n: usize) {
+                <$ty>::add_permits(self, n)
+            }
+        }
+    )*};
+}
+
+impl_semaphore!(CountingSemaphore, WeakSemaphore, StrongSemaphore, PrioritySemaphore);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fairness {
+    // 깨어난 스레드끼리 경쟁한다. 순서 보장이 없다.
+    Weak,
+    // 도착 순서대로 permit을 넘겨준다.
+    #[default]
+    Strong,
+    Priority,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaitStrategy {
+    #[default]
+    Block,
+    // 블록하기 전에 주어진 횟수만큼 try_acquire를 돌아본다. 짧게 잡는 permit에 유리하다.
+    Spin(u32),
+    Yield(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    InitialExceedsMax { initial: usize, max: usize },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InitialExceedsMax { initial, max } => {
+                write!(f, "initial permits ({}) exceed max permits ({})", initial, max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SemaphoreMetrics {
+    pub acquired: u64,
+    pub timed_out: u64,
+    pub rejected: u64,
+    pub released: u64,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEvent {
+    Acquired { waited: Duration },
+    AcquireFailed { waited: Duration, error: AcquireError },
+    Released,
+    // max_permits를 넘는 release라 permit을 늘리지 않고 버렸다.
+    ReleaseRejected { max: usize },
+}
+
+// 호출한 스레드에서 바로 불린다. 스레드 정보가 필요하면 sink 안에서 thread::current()를 보면 된다.
+type TraceFn = dyn Fn(Option<&str>, &TraceEvent) + Send + Sync;
+
+#[derive(Clone)]
+struct TraceSink(Arc<TraceFn>);
+
+impl fmt::Debug for TraceSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TraceSink")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SemaphoreBuilder {
+    name: Option<String>,
+    initial_permits: usize,
+    max_permits: Option<usize>,
+    fairness: Fairness,
+    wait_strategy: WaitStrategy,
+    metrics: bool,
+    tracing: Option<TraceSink>,
+    close_behavior: DrainPolicy,
+}
+
+impl SemaphoreBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            initial_permits: 1,
+            max_permits: None,
+            fairness: Fairness::default(),
+            wait_strategy: WaitStrategy::default(),
+            metrics: false,
+            tracing: None,
+            close_behavior: DrainPolicy::default(),
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    pub fn initial_permits(mut self, permits: usize) -> Self {
+        self.initial_permits = permits;
+        self
+    }
+
+    // 지정하지 않으면 initial_permits와 같다. acquire 없이 release해서 permit을 늘릴 수 있는 상한이다.
+    pub fn max_permits(mut self, permits: usize) -> Self {
+        self.max_permits = Some(permits);
+        self
+    }
+
+    pub fn fairness(mut self, fairness: Fairness) -> Self {
+        self.fairness = fairness;
+        self
+    }
+
+    pub fn wait_strategy(mut self, strategy: WaitStrategy) -> Self {
+        self.wait_strategy = strategy;
+        self
+    }
+
+    pub fn metrics(mut self, enabled: bool) -> Self {
+        self.metrics = enabled;
+        self
+    }
+
+    // acquire/release마다 세마포어 이름과 함께 sink를 부른다.
+    pub fn tracing<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(Option<&str>, &TraceEvent) + Send + Sync + 'static,
+    {
+        self.tracing = Some(TraceSink(Arc::new(sink)));
+        self
+    }
+
+    // drain 중에 새 acquire를 기다리게 할지 바로 실패시킬지 정한다.
+    pub fn close_behavior(mut self, policy: DrainPolicy) -> Self {
+        self.close_behavior = policy;
+        self
+    }
+
+    pub fn build(self) -> Result<Box<dyn Semaphore>, BuildError> {
+        Ok(match self.fairness {
+            Fairness::Weak => Box::new(self.build_weak()?),
+            Fairness::Strong => Box::new(self.build_strong()?),
+            Fairness::Priority => Box::new(self.build_priority()?),
+        })
+    }
+
+    pub fn build_weak(self) -> Result<ConfiguredSemaphore<WeakSemaphore>, BuildError> {
+        let inner = WeakSemaphore::new(self.initial_permits).with_drain_policy(self.close_behavior);
+        self.configure(inner)
+    }
+
+    pub fn build_strong(self) -> Result<ConfiguredSemaphore<StrongSemaphore>, BuildError> {
+        let inner = StrongSemaphore::new(self.initial_permits).with_drain_policy(self.close_behavior);
+        self.configure(inner)
+    }
+
+    pub fn build_priority(self) -> Result<ConfiguredSemaphore<PrioritySemaphore>, BuildError> {
+        let inner = PrioritySemaphore::new(self.initial_permits).with_drain_policy(self.close_behavior);
+        self.configure(inner)
+    }
+
+    fn configure<S: Semaphore>(self, inner: S) -> Result<ConfiguredSemaphore<S>, BuildError> {
+        let max = self.max_permits.unwrap_or(self.initial_permits);
+        if self.initial_permits > max {
+            return Err(BuildError::InitialExceedsMax { initial: self.initial_permits, max });
+        }
+        Ok(ConfiguredSemaphore {
+            inner,
+            name: self.name,
+            max_permits: AtomicUsize::new(max),
+            capacity: AtomicUsize::new(self.initial_permits),
+            held: AtomicUsize::new(0),
+            debt: AtomicUsize::new(0),
+            resize: Mutex::new(()),
+            wait_strategy: self.wait_strategy,
+            metrics: self.metrics.then(|| Mutex::new(SemaphoreMetrics::default())),
+            tracing: self.tracing,
+            tracker: OwnershipTracker::new("ConfiguredSemaphore"),
+        })
+    }
+}
+
+impl Default for SemaphoreBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 빌더가 켠 옵션을 세마포어 바깥에서 적용한다. inner()로 직접 acquire/release하면 permit 집계가 어긋난다.
+pub struct ConfiguredSemaphore<S> {
+    inner: S,
+    name: Option<String>,
+    max_permits: AtomicUsize,
+    // 돌고 있는 permit 총량(남은 것 + 나간 것).
+    capacity: AtomicUsize,
+    // 이 wrapper를 통해 나가서 아직 돌아오지 않은 permit 수.
+    held: AtomicUsize,
+    // permit 수를 줄였지만 아직 나가 있어서 회수하지 못한 만큼. 돌아오는 대로 inner에 돌려주지 않고 없앤다.
+    debt: AtomicUsize,
+    resize: Mutex<()>,
+    wait_strategy: WaitStrategy,
+    metrics: Option<Mutex<SemaphoreMetrics>>,
+    tracing: Option<TraceSink>,
+    tracker: OwnershipTracker,
+}
+
+impl<S: Semaphore> ConfiguredSemaphore<S> {
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn max_permits(&self) -> usize {
+        self.max_permits.load(Ordering::SeqCst)
+    }
+
+    // 돌고 있는 permit 총량. 줄이는 중이면 아직 돌아오지 않은 permit은 빼고 센다.
+    pub fn permits(&self) -> usize {
+        self.capacity.load(Ordering::SeqCst)
+    }
+
+    pub fn held_permits(&self) -> usize {
+        self.held.load(Ordering::SeqCst)
+    }
+
+    // 나가 있는 permit은 건드리지 않는다. 줄일 때 남은 permit이 모자라면 나머지는 돌아오는 permit에서 회수한다.
+    pub fn set_limits(&self, permits: usize, max: usize) -> Result<(), BuildError> {
+        if permits > max {
+            return Err(BuildError::InitialExceedsMax { initial: permits, max });
+        }
+        let _resize = self.resize.lock().unwrap();
+        self.max_permits.store(max, Ordering::SeqCst);
+        let current = self.capacity.swap(permits, Ordering::SeqCst);
+        if permits >= current {
+            self.grow(permits - current);
+        } else {
+            let removed = current - permits;
+            let forgotten = self.inner.forget_permits(removed);
+            self.debt.fetch_add(removed - forgotten, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    // 나간 permit이 없을 때의 release는 max_permits까지 permit을 늘린다. 상한을 넘으면 아무것도 바꾸지 않는다.
+    pub fn try_release(&self) -> Result<(), ReleaseError> {
+        // debt를 보고 inner에 돌려주는 사이에 set_limits가 끼어들면 permit이 두 번 회수되므로 resize를 잡는다.
+        let resize = self.resize.lock().unwrap();
+        let returned = self
+            .held
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |held| held.checked_sub(1))
+            .is_ok();
+        if !returned {
+            let max = self.max_permits();
+            if self
+                .capacity
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |capacity| (capacity < max).then_some(capacity + 1))
+                .is_err()
+            {
+                drop(resize);
+                self.trace(&TraceEvent::ReleaseRejected { max });
+                return Err(ReleaseError::ExceedsMax { max });
+            }
+            self.grow(1);
+        } else if self.debt.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |d| d.checked_sub(1)).is_ok() {
+            self.inner.forget_held_permit();
+        } else {
+            self.inner.release();
+        }
+        drop(resize);
+        if let Some(metrics) = &self.metrics {
+            metrics.lock().unwrap().released += 1;
+        }
+        self.trace(&TraceEvent::Released);
+        Ok(())
+    }
+
+    // capacity는 이미 늘려 둔 상태에서 부른다. 아직 회수하지 못한 debt가 있으면 그만큼은 inner에 더하지 않고 debt를 지운다.
+    fn grow(&self, mut added: usize) {
+        while added > 0 && self.debt.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |d| d.checked_sub(1)).is_ok() {
+            added -= 1;
+        }
+        self.inner.add_permits(added);
+    }
+
+    fn trace(&self, event: &TraceEvent) {
+        if let Some(TraceSink(sink)) = &self.tracing {
+            sink(self.name.as_deref(), event);
+        }
+    }
+
+    // 대기 전략에 따라 블록하기 전에 잠깐 돌아본다. drain으로 거절되면 곧바로 블록 경로로 넘긴다.
+    fn spin(&self) -> bool {
+        let (attempts, yield_now) = match self.wait_strategy {
+            WaitStrategy::Block => return false,
+            WaitStrategy::Spin(attempts) => (attempts, false),
+            WaitStrategy::Yield(attempts) => (attempts, true),
+        };
+        for _ in 0..attempts {
+            match self.inner.try_acquire() {
+                Ok(()) => return true,
+                Err(AcquireError::WouldBlock) => {}
+                Err(_) => return false,
+            }
+            if yield_now {
+                thread::yield_now();
+            } else {
+                hint::spin_loop();
+            }
+        }
+        false
+    }
+
+    fn record(&self, started: Instant, result: Result<(), AcquireError>) -> Result<(), AcquireError> {
+        let waited = started.elapsed();
+        if result.is_ok() {
+            self.held.fetch_add(1, Ordering::SeqCst);
+        }
+        if let Some(metrics) = &self.metrics {
+            let mut metrics = metrics.lock().unwrap();
+            match result {
+                Ok(()) => {
+                    metrics.acquired += 1;
+                    metrics.total_wait += waited;
+                    metrics.max_wait = metrics.max_wait.max(waited);
+                }
+                Err(AcquireError::Timeout) => metrics.timed_out += 1,
+                Err(_) => metrics.rejected += 1,
+            }
+        }
+        self.trace(&match result {
+            Ok(()) => TraceEvent::Acquired { waited },
+            Err(error) => TraceEvent::AcquireFailed { waited, error },
+        });
+        result
+    }
+}
+
+impl<S: Semaphore> Semaphore for ConfiguredSemaphore<S> {
+    fn acquire(&self) {
+        let started = Instant::now();
+        if !self.spin() {
+            self.inner.acquire();
+        }
+        let _ = self.record(started, Ok(()));
+    }
+
+    fn try_acquire(&self) -> Result<(), AcquireError> {
+        let started = Instant::now();
+        let result = self.inner.try_acquire();
+        self.record(started, result)
+    }
+
+    fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        let started = Instant::now();
+        let result = if self.spin() {
+            Ok(())
+        } else {
+            self.inner.acquire_timeout(timeout.saturating_sub(started.elapsed()))
+        };
+        self.record(started, result)
+    }
+
+    // max_permits를 넘는 release는 무시하고 ownership tracker로 보고한다. 결과가 필요하면 try_release를 쓴다.
+    fn release(&self) {
+        if let Err(ReleaseError::ExceedsMax { max }) = self.try_release() {
+            self.tracker.report(Misuse::ReleaseAboveMax { max });
+        }
+    }
+
+    fn available_permits(&self) -> usize {
+        self.inner.available_permits()
+    }
+
+    fn waiting_threads(&self) -> usize {
+        self.inner.waiting_threads()
+    }
+
+    fn drain(&self) {
+        self.inner.drain()
+    }
+
+    fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.inner.drain_timeout(timeout)
+    }
+
+    fn reopen(&self) {
+        self.inner.reopen()
+    }
+
+    fn is_draining(&self) -> bool {
+        self.inner.is_draining()
+    }
+
+    // wrapper가 집계하는 permit 총량도 같이 맞춘다.
+    fn forget_permits(&self, n: usize) -> usize {
+        let _resize = self.resize.lock().unwrap();
+        let forgotten = self.inner.forget_permits(n);
+        self.capacity.fetch_sub(forgotten, Ordering::SeqCst);
+        forgotten
+    }
+
+    fn forget_held_permit(&self) {
+        if self.held.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |held| held.checked_sub(1)).is_ok() {
+            self.capacity.fetch_sub(1, Ordering::SeqCst);
+            self.inner.forget_held_permit();
+        }
+    }
+
+    // set_limits와 달리 max_permits는 그대로 두고, 넘치는 만큼은 늘리지 않는다.
+    fn add_permits(&self, n: usize) {
+        let _resize = self.resize.lock().unwrap();
+        let max = self.max_permits();
+        let mut added = 0;
+        let _ = self.capacity.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |capacity| {
+            added = n.min(max.saturating_sub(capacity));
+            Some(capacity + added)
+        });
+        self.grow(added);
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    fn metrics(&self) -> Option<SemaphoreMetrics> {
+        self.metrics.as_ref().map(|metrics| *metrics.lock().unwrap())
+    }
+}
+
+impl ConfiguredSemaphore<PrioritySemaphore> {
+    pub fn acquire_with_priority(&self, priority: u32) {
+        let started = Instant::now();
+        self.inner.acquire_with_priority(priority);
+        let _ = self.record(started, Ok(()));
+    }
+
+    pub fn acquire_with_priority_timeout(&self, priority: u32, timeout: Duration) -> Result<(), AcquireError> {
+        let started = Instant::now();
+        let result = self.inner.acquire_with_priority_timeout(priority, timeout);
+        self.record(started, result)
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for ConfiguredSemaphore<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfiguredSemaphore")
+            .field("name", &self.name)
+            .field("permits", &self.capacity.load(Ordering::SeqCst))
+            .field("max_permits", &self.max_permits.load(Ordering::SeqCst))
+            .field("held", &self.held.load(Ordering::SeqCst))
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
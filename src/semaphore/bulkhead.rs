@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+use super::permit::Permit;
+
+// 대기열 길이와 대기 시간을 모두 제한해서, 과부하 시 지연이 쌓이는 대신 바로 거절한다.
+pub struct Bulkhead {
+    semaphore: CountingSemaphore,
+    max_concurrent: usize,
+    max_waiters: usize,
+    max_wait: Duration,
+    waiting: AtomicUsize,
+    rejected: AtomicUsize,
+    timed_out: AtomicUsize,
+}
+
+impl Bulkhead {
+    pub fn new(max_concurrent: usize, max_waiters: usize, max_wait: Duration) -> Self {
+        Self {
+            semaphore: CountingSemaphore::untracked(max_concurrent),
+            max_concurrent,
+            max_waiters,
+            max_wait,
+            waiting: AtomicUsize::new(0),
+            rejected: AtomicUsize::new(0),
+            timed_out: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::SeqCst)
+    }
+
+    pub fn rejected_count(&self) -> usize {
+        self.rejected.load(Ordering::SeqCst)
+    }
+
+    pub fn timed_out_count(&self) -> usize {
+        self.timed_out.load(Ordering::SeqCst)
+    }
+
+    pub fn acquire(&self) -> Result<Permit<'_>, AcquireError> {
+        if self.semaphore.try_acquire().is_ok() {
+            return Ok(Permit::new(&self.semaphore, 1));
+        }
+
+        let entered = self.waiting.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |waiting| {
+            (waiting < self.max_waiters).then_some(waiting + 1)
+        });
+        if entered.is_err() {
+            self.rejected.fetch_add(1, Ordering::SeqCst);
+            return Err(AcquireError::Rejected);
+        }
+
+        let result = self.semaphore.acquire_timeout(self.max_wait);
+        self.waiting.fetch_sub(1, Ordering::SeqCst);
+        match result {
+            Ok(()) => Ok(Permit::new(&self.semaphore, 1)),
+            Err(err) => {
+                self.timed_out.fetch_add(1, Ordering::SeqCst);
+                Err(err)
+            }
+        }
+    }
+
+    pub fn call<F, R>(&self, f: F) -> Result<R, AcquireError>
+    where
+        F: FnOnce() -> R,
+    {
+        let _permit = self.acquire()?;
+        Ok(f())
+    }
+}
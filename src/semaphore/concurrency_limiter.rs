@@ -0,0 +1,303 @@
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use super::clock::{Clock, SystemClock};
+use super::error::AcquireError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    // 과부하로 인한 실패(타임아웃, 거절 등). 한도를 줄이는 신호로 쓰인다.
+    Dropped,
+    // 한도 계산에 반영하지 않는다. 보고 없이 permit을 drop한 경우도 여기에 해당한다.
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub latency: Duration,
+    pub in_flight: usize,
+    pub outcome: Outcome,
+}
+
+pub trait LimitAlgorithm: Send {
+    fn initial_limit(&self) -> usize;
+    fn update(&mut self, limit: usize, sample: &Sample) -> usize;
+}
+
+// 성공하면 1씩 늘리고, 실패하거나 timeout보다 느리면 backoff 비율로 줄인다.
+pub struct Aimd {
+    initial: usize,
+    min: usize,
+    max: usize,
+    backoff: f64,
+    timeout: Duration,
+}
+
+impl Aimd {
+    pub fn new(initial: usize, min: usize, max: usize) -> Self {
+        assert!(min > 0 && min <= initial && initial <= max, "limits must satisfy 0 < min <= initial <= max");
+        Self { initial, min, max, backoff: 0.9, timeout: Duration::from_secs(5) }
+    }
+
+    pub fn with_backoff(mut self, backoff: f64) -> Self {
+        assert!(backoff > 0.0 && backoff < 1.0, "backoff must be in (0, 1)");
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl LimitAlgorithm for Aimd {
+    fn initial_limit(&self) -> usize {
+        self.initial
+    }
+
+    fn update(&mut self, limit: usize, sample: &Sample) -> usize {
+        let limit = match sample.outcome {
+            Outcome::Ignore => limit,
+            Outcome::Dropped => (limit as f64 * self.backoff) as usize,
+            Outcome::Success if sample.latency > self.timeout => (limit as f64 * self.backoff) as usize,
+            // 한도를 절반도 쓰지 않는 동안에는 늘릴 근거가 없다.
+            Outcome::Success if sample.in_flight * 2 < limit => limit,
+            Outcome::Success => limit + 1,
+        };
+        limit.clamp(self.min, self.max)
+    }
+}
+
+// TCP Vegas처럼 최소 지연(base_rtt) 대비 현재 지연으로 큐에 쌓인 요청 수를 추정한다.
+pub struct Vegas {
+    initial: usize,
+    min: usize,
+    max: usize,
+    alpha: usize,
+    beta: usize,
+    base_rtt: Option<Duration>,
+}
+
+impl Vegas {
+    pub fn new(initial: usize, min: usize, max: usize) -> Self {
+        assert!(min > 0 && min <= initial && initial <= max, "limits must satisfy 0 < min <= initial <= max");
+        Self { initial, min, max, alpha: 3, beta: 6, base_rtt: None }
+    }
+
+    pub fn with_thresholds(mut self, alpha: usize, beta: usize) -> Self {
+        assert!(alpha < beta, "alpha must be smaller than beta");
+        self.alpha = alpha;
+        self.beta = beta;
+        self
+    }
+}
+
+impl LimitAlgorithm for Vegas {
+    fn initial_limit(&self) -> usize {
+        self.initial
+    }
+
+    fn update(&mut self, limit: usize, sample: &Sample) -> usize {
+        match sample.outcome {
+            Outcome::Ignore => return limit,
+            Outcome::Dropped => return (limit / 2).clamp(self.min, self.max),
+            Outcome::Success => {}
+        }
+
+        let rtt = sample.latency.max(Duration::from_nanos(1));
+        let base_rtt = *self.base_rtt.get_or_insert(rtt);
+        let base_rtt = base_rtt.min(rtt);
+        self.base_rtt = Some(base_rtt);
+
+        let queue = (limit as f64 * (1.0 - base_rtt.as_secs_f64() / rtt.as_secs_f64())).ceil() as usize;
+        let limit = if queue < self.alpha {
+            limit + 1
+        } else if queue > self.beta {
+            limit.saturating_sub(1)
+        } else {
+            limit
+        };
+        limit.clamp(self.min, self.max)
+    }
+}
+
+// 장기 평균 지연과 최근 지연의 비율(gradient)로 한도를 부드럽게 조정한다.
+pub struct Gradient {
+    min: usize,
+    max: usize,
+    estimated: f64,
+    smoothing: f64,
+    long_rtt: Option<f64>,
+    long_window: f64,
+}
+
+impl Gradient {
+    pub fn new(initial: usize, min: usize, max: usize) -> Self {
+        assert!(min > 0 && min <= initial && initial <= max, "limits must satisfy 0 < min <= initial <= max");
+        Self { min, max, estimated: initial as f64, smoothing: 0.2, long_rtt: None, long_window: 100.0 }
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        assert!(smoothing > 0.0 && smoothing <= 1.0, "smoothing must be in (0, 1]");
+        self.smoothing = smoothing;
+        self
+    }
+}
+
+impl LimitAlgorithm for Gradient {
+    fn initial_limit(&self) -> usize {
+        self.estimated as usize
+    }
+
+    fn update(&mut self, limit: usize, sample: &Sample) -> usize {
+        let gradient = match sample.outcome {
+            Outcome::Ignore => return limit,
+            Outcome::Dropped => 0.5,
+            Outcome::Success => {
+                let short = sample.latency.as_secs_f64().max(1e-9);
+                let long = match self.long_rtt {
+                    Some(long) => long + (short - long) / self.long_window,
+                    None => short,
+                };
+                self.long_rtt = Some(long);
+                (long / short).clamp(0.5, 1.0)
+            }
+        };
+
+        let queue = self.estimated.sqrt();
+        let target = self.estimated * gradient + queue;
+        self.estimated = (self.estimated * (1.0 - self.smoothing) + target * self.smoothing)
+            .clamp(self.min as f64, self.max as f64);
+        self.estimated as usize
+    }
+}
+
+struct LimiterState {
+    limit: usize,
+    in_flight: usize,
+    algorithm: Box<dyn LimitAlgorithm>,
+}
+
+// CountingSemaphore와 같은 count + Condvar 구조이지만, 남은 permit 대신 limit과 in_flight를 따로 들고 있어
+// 한도가 줄어들 때 이미 나간 permit을 회수하지 않고 반납되기를 기다린다.
+pub struct ConcurrencyLimiter<C: Clock = SystemClock> {
+    state: Mutex<LimiterState>,
+    cond: Condvar,
+    clock: C,
+}
+
+impl ConcurrencyLimiter<SystemClock> {
+    pub fn new<A: LimitAlgorithm + 'static>(algorithm: A) -> Self {
+        Self::with_clock(algorithm, SystemClock::new())
+    }
+}
+
+impl<C: Clock> ConcurrencyLimiter<C> {
+    pub fn with_clock<A: LimitAlgorithm + 'static>(algorithm: A, clock: C) -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                limit: algorithm.initial_limit().max(1),
+                in_flight: 0,
+                algorithm: Box::new(algorithm),
+            }),
+            cond: Condvar::new(),
+            clock,
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.state.lock().unwrap().in_flight
+    }
+
+    pub fn acquire(&self) -> ConcurrencyPermit<'_, C> {
+        let mut state = self.state.lock().unwrap();
+        while state.in_flight >= state.limit {
+            state = self.cond.wait(state).unwrap();
+        }
+        state.in_flight += 1;
+        self.permit(state.in_flight)
+    }
+
+    pub fn try_acquire(&self) -> Result<ConcurrencyPermit<'_, C>, AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if state.in_flight >= state.limit {
+            return Err(AcquireError::WouldBlock);
+        }
+        state.in_flight += 1;
+        Ok(self.permit(state.in_flight))
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<ConcurrencyPermit<'_, C>, AcquireError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock().unwrap();
+        while state.in_flight >= state.limit {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(AcquireError::Timeout);
+            }
+            state = self.cond.wait_timeout(state, deadline - now).unwrap().0;
+        }
+        state.in_flight += 1;
+        Ok(self.permit(state.in_flight))
+    }
+
+    fn permit(&self, in_flight: usize) -> ConcurrencyPermit<'_, C> {
+        ConcurrencyPermit {
+            limiter: self,
+            started: self.clock.now(),
+            in_flight,
+            outcome: Outcome::Ignore,
+        }
+    }
+
+    fn complete(&self, sample: Sample) {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight -= 1;
+        let old_limit = state.limit;
+        if sample.outcome != Outcome::Ignore {
+            state.limit = state.algorithm.update(old_limit, &sample).max(1);
+        }
+        if state.limit > old_limit {
+            self.cond.notify_all();
+        } else {
+            self.cond.notify_one();
+        }
+    }
+}
+
+pub struct ConcurrencyPermit<'a, C: Clock = SystemClock> {
+    limiter: &'a ConcurrencyLimiter<C>,
+    started: Duration,
+    in_flight: usize,
+    outcome: Outcome,
+}
+
+impl<C: Clock> ConcurrencyPermit<'_, C> {
+    pub fn success(mut self) {
+        self.outcome = Outcome::Success;
+    }
+
+    pub fn dropped(mut self) {
+        self.outcome = Outcome::Dropped;
+    }
+
+    pub fn ignore(mut self) {
+        self.outcome = Outcome::Ignore;
+    }
+}
+
+impl<C: Clock> Drop for ConcurrencyPermit<'_, C> {
+    fn drop(&mut self) {
+        let latency = self.limiter.clock.now().saturating_sub(self.started);
+        self.limiter.complete(Sample {
+            latency,
+            in_flight: self.in_flight,
+            outcome: self.outcome,
+        });
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+use super::builder::{BuildError, Fairness};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemaphoreConfig {
+    pub name: String,
+    pub kind: Fairness,
+    pub permits: usize,
+    // 없으면 permits와 같다.
+    pub max: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    Io(String),
+    Parse { line: usize, message: String },
+    MissingField { name: Option<String>, field: &'static str },
+    UnknownField(String),
+    UnknownKind(String),
+    Duplicate(String),
+    KindChanged(String),
+    Build { name: String, error: BuildError },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(message) => write!(f, "failed to read config: {}", message),
+            ConfigError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            ConfigError::MissingField { name: Some(name), field } => write!(f, "semaphore {} is missing {}", name, field),
+            ConfigError::MissingField { name: None, field } => write!(f, "semaphore entry is missing {}", field),
+            ConfigError::UnknownField(field) => write!(f, "unknown field {}", field),
+            ConfigError::UnknownKind(kind) => write!(f, "unknown semaphore kind {}", kind),
+            ConfigError::Duplicate(name) => write!(f, "semaphore {} is defined twice", name),
+            ConfigError::KindChanged(name) => write!(f, "semaphore {} cannot change kind on reload", name),
+            ConfigError::Build { name, error } => write!(f, "semaphore {}: {}", name, error),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Str(String),
+    Int(usize),
+}
+
+type Fields = Vec<(String, Value)>;
+
+// 설정 파일 형식과 상관없이 항목 하나를 key/value 목록으로 모은 뒤 SemaphoreConfig로 바꾼다.
+fn to_config(fields: Fields, line: usize) -> Result<SemaphoreConfig, ConfigError> {
+    let (mut name, mut kind, mut permits, mut max) = (None, None, None, None);
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            ("name", Value::Str(value)) => name = Some(value),
+            ("kind", Value::Str(value)) => kind = Some(parse_kind(&value)?),
+            ("permits", Value::Int(value)) => permits = Some(value),
+            ("max", Value::Int(value)) => max = Some(value),
+            ("name" | "kind" | "permits" | "max", _) => {
+                return Err(ConfigError::Parse { line, message: format!("{} has the wrong type", key) })
+            }
+            _ => return Err(ConfigError::UnknownField(key)),
+        }
+    }
+    let name = name.ok_or(ConfigError::MissingField { name: None, field: "name" })?;
+    let permits = match permits {
+        Some(permits) => permits,
+        None => return Err(ConfigError::MissingField { name: Some(name), field: "permits" }),
+    };
+    Ok(SemaphoreConfig { name, kind: kind.unwrap_or_default(), permits, max })
+}
+
+fn parse_kind(kind: &str) -> Result<Fairness, ConfigError> {
+    match kind {
+        "weak" => Ok(Fairness::Weak),
+        "strong" => Ok(Fairness::Strong),
+        "priority" => Ok(Fairness::Priority),
+        _ => Err(ConfigError::UnknownKind(kind.to_owned())),
+    }
+}
+
+fn check_duplicates(configs: &[SemaphoreConfig]) -> Result<(), ConfigError> {
+    let mut seen = HashSet::new();
+    for config in configs {
+        if !seen.insert(config.name.as_str()) {
+            return Err(ConfigError::Duplicate(config.name.clone()));
+        }
+    }
+    Ok(())
+}
+
+// [[semaphore]] 테이블 배열만 지원하는 TOML 부분집합. 값은 문자열과 정수만 받는다.
+//
+// [[semaphore]]
+// name = "db-writes"
+// kind = "strong"
+// permits = 4
+// max = 8
+pub fn parse_toml(input: &str) -> Result<Vec<SemaphoreConfig>, ConfigError> {
+    let mut configs = Vec::new();
+    let mut current: Option<(usize, Fields)> = None;
+
+    for (index, raw) in input.lines().enumerate() {
+        let line = index + 1;
+        let text = strip_toml_comment(raw).trim();
+        if text.is_empty() {
+            continue;
+        }
+        if text.starts_with('[') {
+            if text != "[[semaphore]]" {
+                return Err(ConfigError::Parse { line, message: format!("unsupported table {}", text) });
+            }
+            if let Some((start, fields)) = current.take() {
+                configs.push(to_config(fields, start)?);
+            }
+            current = Some((line, Vec::new()));
+            continue;
+        }
+
+        let (key, value) = text
+            .split_once('=')
+            .ok_or_else(|| ConfigError::Parse { line, message: "expected key = value".into() })?;
+        let value = parse_toml_value(value.trim())
+            .ok_or_else(|| ConfigError::Parse { line, message: format!("invalid value {}", value.trim()) })?;
+        match current.as_mut() {
+            Some((_, fields)) => fields.push((key.trim().to_owned(), value)),
+            None => return Err(ConfigError::Parse { line, message: "key outside of [[semaphore]]".into() }),
+        }
+    }
+    if let Some((start, fields)) = current {
+        configs.push(to_config(fields, start)?);
+    }
+    check_duplicates(&configs)?;
+    Ok(configs)
+}
+
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_toml_value(text: &str) -> Option<Value> {
+    if let Some(inner) = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        return (!inner.contains('"')).then(|| Value::Str(inner.to_owned()));
+    }
+    text.replace('_', "").parse().ok().map(Value::Int)
+}
+
+// 평평한 객체의 배열만 받는다. 최상위가 {"semaphores": [...]}여도 된다.
+pub fn parse_json(input: &str) -> Result<Vec<SemaphoreConfig>, ConfigError> {
+    let mut parser = JsonParser { chars: input.chars().peekable(), line: 1 };
+    parser.skip_whitespace();
+    let entries = if parser.peek() == Some('{') {
+        parser.expect('{')?;
+        let key = parser.string()?;
+        if key != "semaphores" {
+            return Err(ConfigError::UnknownField(key));
+        }
+        parser.expect(':')?;
+        let entries = parser.array()?;
+        parser.expect('}')?;
+        entries
+    } else {
+        parser.array()?
+    };
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error("trailing characters"));
+    }
+
+    let configs = entries
+        .into_iter()
+        .map(|(line, fields)| to_config(fields, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    check_duplicates(&configs)?;
+    Ok(configs)
+}
+
+struct JsonParser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> ConfigError {
+        ConfigError::Parse { line: self.line, message: message.to_owned() }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ConfigError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    // 다음 문자가 separator면 먹고 true, close면 먹고 false.
+    fn separator(&mut self, close: char) -> Result<bool, ConfigError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(',') => Ok(true),
+            Some(c) if c == close => Ok(false),
+            _ => Err(self.error(&format!("expected ',' or '{}'", close))),
+        }
+    }
+
+    fn array(&mut self) -> Result<Vec<(usize, Fields)>, ConfigError> {
+        self.expect('[')?;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(entries);
+        }
+        loop {
+            self.skip_whitespace();
+            let line = self.line;
+            entries.push((line, self.object()?));
+            if !self.separator(']')? {
+                return Ok(entries);
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Fields, ConfigError> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(fields);
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            fields.push((key, self.value()?));
+            if !self.separator('}')? {
+                return Ok(fields);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, ConfigError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => self.string().map(Value::Str),
+            Some(c) if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+                    digits.push(c);
+                    self.next();
+                }
+                digits.parse().map(Value::Int).map_err(|_| self.error("number out of range"))
+            }
+            _ => Err(self.error("expected a string or a non-negative integer")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ConfigError> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => match self.next() {
+                    Some(c @ ('"' | '\\' | '/')) => text.push(c),
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    _ => return Err(self.error("unsupported escape")),
+                },
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+struct LatchState {
+    count: usize,
+    waiting: usize,
+}
+
+pub struct CountDownLatch {
+    state: Mutex<LatchState>,
+    gate: CountingSemaphore,
+}
+
+impl CountDownLatch {
+    pub fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new(LatchState { count, waiting: 0 }),
+            gate: CountingSemaphore::untracked(0),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.state.lock().unwrap().count
+    }
+
+    pub fn count_down(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.count == 0 {
+            return;
+        }
+        state.count -= 1;
+        if state.count == 0 {
+            self.gate.release_many(state.waiting);
+            state.waiting = 0;
+        }
+    }
+
+    pub fn wait(&self) {
+        if self.enter() {
+            self.gate.acquire();
+        }
+    }
+
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        if !self.enter() {
+            return Ok(());
+        }
+        if self.gate.acquire_timeout(timeout).is_ok() {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.count > 0 {
+            state.waiting -= 1;
+            return Err(AcquireError::Timeout);
+        }
+        drop(state);
+
+        self.gate.acquire();
+        Ok(())
+    }
+
+    // 아직 열리지 않았다면 대기자로 등록하고 true를 돌려준다.
+    fn enter(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.count == 0 {
+            return false;
+        }
+        state.waiting += 1;
+        true
+    }
+}
@@ -1,29 +1,300 @@
-use std::sync::{Condvar, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use super::drain::{self, DrainPolicy, DrainState};
+use super::error::AcquireError;
+use super::ownership::OwnershipTracker;
+use super::pause::PauseState;
+use super::select::SelectWaker;
+use super::watchdog::{HoldSlot, Watchdog};
+
+struct State {
+    permits: usize,
+    waiting: usize,
+    drain: DrainState,
+    pause: PauseState,
+}
+
+impl State {
+    fn admits(&self, n: usize) -> bool {
+        !self.drain.draining && !self.pause.paused && self.permits >= n
+    }
+}
 
 pub struct CountingSemaphore {
-    count: Mutex<usize>,
+    state: Mutex<State>,
     cond: Condvar,
+    drained: Condvar,
+    // 한 번에 여러 permit을 기다리는 스레드가 있으면 notify_one으로는 wakeup을 잃을 수 있다.
+    wide_waiters: AtomicUsize,
+    // select_acquire로 이 세마포어를 지켜보는 스레드들.
+    watchers: Mutex<Vec<Arc<SelectWaker>>>,
+    watcher_count: AtomicUsize,
+    tracker: OwnershipTracker,
+    holds: HoldSlot,
 }
 
 impl CountingSemaphore {
     pub fn new(count: usize) -> Self {
+        Self::with_tracker(count, OwnershipTracker::new("CountingSemaphore"))
+    }
+
+    // 신호 용도로 쓰이거나 guard가 다른 스레드에서 drop될 수 있어, 다른 스레드가 release하는 것이 정상인 내부 세마포어는 소유권을 추적하지 않는다.
+    pub(crate) fn untracked(count: usize) -> Self {
+        Self::with_tracker(count, OwnershipTracker::disabled())
+    }
+
+    fn with_tracker(count: usize, tracker: OwnershipTracker) -> Self {
         Self {
-            count: Mutex::new(count),
+            state: Mutex::new(State { permits: count, waiting: 0, drain: DrainState::default(), pause: PauseState::default() }),
             cond: Condvar::new(),
+            drained: Condvar::new(),
+            wide_waiters: AtomicUsize::new(0),
+            watchers: Mutex::new(Vec::new()),
+            watcher_count: AtomicUsize::new(0),
+            tracker,
+            holds: HoldSlot::default(),
         }
     }
 
+    pub fn with_drain_policy(mut self, policy: DrainPolicy) -> Self {
+        self.state.get_mut().unwrap().drain.policy = policy;
+        self
+    }
+
     pub fn acquire(&self) {
-        let mut count = self.count.lock().unwrap();
-        while *count == 0{
-            count = self.cond.wait(count).unwrap();
+        let mut state = self.state.lock().unwrap();
+        state.pause.on_arrival();
+        if !state.admits(1) {
+            state.waiting += 1;
+            while !state.admits(1) {
+                state = self.cond.wait(state).unwrap();
+            }
+            state.waiting -= 1;
+        }
+        state.permits -= 1;
+        state.drain.on_grant(1);
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        self.try_acquire_many(1)
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.acquire_many_timeout(1, timeout)
+    }
+
+    pub fn acquire_many(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.pause.on_arrival();
+        if !state.admits(n) {
+            state.waiting += 1;
+            self.wide_waiters.fetch_add((n > 1) as usize, Ordering::SeqCst);
+            while !state.admits(n) {
+                state = self.cond.wait(state).unwrap();
+            }
+            self.wide_waiters.fetch_sub((n > 1) as usize, Ordering::SeqCst);
+            state.waiting -= 1;
+        }
+        state.permits -= n;
+        state.drain.on_grant(n);
+        self.tracker.on_acquire(n);
+        self.holds.on_acquire(n);
+    }
+
+    pub fn try_acquire_many(&self, n: usize) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if state.drain.rejects() {
+            return Err(AcquireError::Draining);
+        }
+        if !state.admits(n) {
+            return Err(AcquireError::WouldBlock);
+        }
+        state.permits -= n;
+        state.drain.on_grant(n);
+        self.tracker.on_acquire(n);
+        self.holds.on_acquire(n);
+        Ok(())
+    }
+
+    pub fn acquire_many_timeout(&self, n: usize, timeout: Duration) -> Result<(), AcquireError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock().unwrap();
+        state.pause.on_arrival();
+        if !state.admits(n) {
+            state.waiting += 1;
+            self.wide_waiters.fetch_add((n > 1) as usize, Ordering::SeqCst);
+            while !state.admits(n) {
+                let now = Instant::now();
+                let error = if state.drain.rejects() {
+                    AcquireError::Draining
+                } else if now >= deadline {
+                    AcquireError::Timeout
+                } else {
+                    state = self.cond.wait_timeout(state, deadline - now).unwrap().0;
+                    continue;
+                };
+                self.wide_waiters.fetch_sub((n > 1) as usize, Ordering::SeqCst);
+                state.waiting -= 1;
+                return Err(error);
+            }
+            self.wide_waiters.fetch_sub((n > 1) as usize, Ordering::SeqCst);
+            state.waiting -= 1;
         }
-        *count -= 1;
+        state.permits -= n;
+        state.drain.on_grant(n);
+        self.tracker.on_acquire(n);
+        self.holds.on_acquire(n);
+        Ok(())
+    }
+
+    // 이미 감시 중이면 false를 돌려준다.
+    pub fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.holds.watch(watchdog, name)
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        self.state.lock().unwrap().waiting
+    }
+
+    // permit 수 자체를 줄인다. 남은 permit 중 최대 n개를 없애고 실제로 없앤 수를 돌려준다.
+    pub fn forget_permits(&self, n: usize) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let forgotten = n.min(state.permits);
+        state.permits -= forgotten;
+        forgotten
+    }
+
+    // 들고 있던 permit을 돌려주지 않고 없앤다. drain과 소유권 집계에는 반납한 것으로 처리된다.
+    pub fn forget_held_permit(&self) {
+        self.holds.on_release(1);
+        if self.state.lock().unwrap().drain.on_return(1) {
+            self.drained.notify_all();
+        }
+        self.tracker.on_release(1);
+    }
+
+    // release와 달리 들고 있던 permit을 돌려주는 것이 아니라 permit 수 자체를 늘린다. 소유권이나 drain 집계에는 잡히지 않는다.
+    pub fn add_permits(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        self.state.lock().unwrap().permits += n;
+        self.cond.notify_all();
+        self.wake_watchers();
+    }
+
+    // 새 permit 발급을 멈추고 나간 permit이 모두 돌아올 때까지 기다린다. reopen 전까지 drain 상태가 유지된다.
+    pub fn drain(&self) {
+        let _ = self.drain_inner(None);
+    }
+
+    // 타임아웃되어도 drain 상태는 풀리지 않는다. 다시 drain하거나 reopen해야 한다.
+    pub fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.drain_inner(Some(timeout))
+    }
+
+    pub fn reopen(&self) {
+        self.state.lock().unwrap().drain.draining = false;
+        self.cond.notify_all();
+        self.wake_watchers();
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.state.lock().unwrap().drain.draining
+    }
+
+    // release는 계속 받지만 resume 전까지 permit을 내주지 않는다.
+    pub fn pause(&self) {
+        self.state.lock().unwrap().pause.paused = true;
+    }
+
+    pub fn resume(&self) {
+        self.state.lock().unwrap().pause.paused = false;
+        self.cond.notify_all();
+        self.wake_watchers();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().pause.paused
+    }
+
+    pub fn blocked_by_pause(&self) -> usize {
+        self.state.lock().unwrap().pause.blocked
+    }
+
+    fn drain_inner(&self, timeout: Option<Duration>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = true;
+        if state.drain.policy == DrainPolicy::Reject {
+            self.cond.notify_all();
+        }
+        drain::wait_drained(state, &self.drained, |s| &s.drain, timeout)
     }
 
     pub fn release(&self) {
-        let mut count = self.count.lock().unwrap();
-        *count += 1;
-        self.cond.notify_one();
+        self.release_many(1);
+    }
+
+    pub fn release_many(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+        self.holds.on_release(n);
+        let mut state = self.state.lock().unwrap();
+        state.permits += n;
+        if state.drain.on_return(n) {
+            self.drained.notify_all();
+        }
+        if n == 1 && self.wide_waiters.load(Ordering::SeqCst) == 0 {
+            self.cond.notify_one();
+        } else {
+            self.cond.notify_all();
+        }
+        drop(state);
+        self.wake_watchers();
+        // 보고는 permit을 돌려준 뒤에 한다. reporter가 panic해도 세마포어 상태는 일관되게 남는다.
+        self.tracker.on_release(n);
+    }
+
+    fn wake_watchers(&self) {
+        if self.watcher_count.load(Ordering::SeqCst) > 0 {
+            for watcher in self.watchers.lock().unwrap().iter() {
+                watcher.wake();
+            }
+        }
     }
-}
\ No newline at end of file
+
+    pub(crate) fn add_select_waker(&self, waker: &Arc<SelectWaker>) {
+        let mut watchers = self.watchers.lock().unwrap();
+        watchers.push(Arc::clone(waker));
+        self.watcher_count.store(watchers.len(), Ordering::SeqCst);
+    }
+
+    pub(crate) fn remove_select_waker(&self, waker: &Arc<SelectWaker>) {
+        let mut watchers = self.watchers.lock().unwrap();
+        if let Some(index) = watchers.iter().position(|w| Arc::ptr_eq(w, waker)) {
+            watchers.swap_remove(index);
+        }
+        self.watcher_count.store(watchers.len(), Ordering::SeqCst);
+    }
+}
+
+impl fmt::Debug for CountingSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("CountingSemaphore")
+            .field("available_permits", &state.permits)
+            .field("waiting_threads", &state.waiting)
+            .field("draining", &state.drain.draining)
+            .field("paused", &state.pause.paused)
+            .finish()
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::{Condvar, MutexGuard};
+use std::time::{Duration, Instant};
+use super::error::AcquireError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrainPolicy {
+    // drain 중 새 acquire는 reopen될 때까지 기다린다.
+    #[default]
+    Wait,
+    // 실패를 돌려줄 수 있는 acquire는 Draining으로 바로 실패한다. 기존 acquire()는 여전히 기다린다.
+    Reject,
+}
+
+// 세마포어 state 안에 넣어 같은 lock 아래에서 나간 permit 수와 drain 여부를 관리한다.
+#[derive(Debug, Default)]
+pub(crate) struct DrainState {
+    pub(crate) policy: DrainPolicy,
+    pub(crate) draining: bool,
+    // acquire로 나간 뒤 아직 돌아오지 않은 permit 수.
+    // 신호 용도로 acquire 없이 release되는 경우가 있어 0 아래로는 내려가지 않는다.
+    pub(crate) outstanding: usize,
+}
+
+impl DrainState {
+    pub(crate) fn on_grant(&mut self, n: usize) {
+        self.outstanding += n;
+    }
+
+    // drain 중에 마지막 permit이 돌아오면 true.
+    pub(crate) fn on_return(&mut self, n: usize) -> bool {
+        self.outstanding = self.outstanding.saturating_sub(n);
+        self.draining && self.outstanding == 0
+    }
+
+    pub(crate) fn rejects(&self) -> bool {
+        self.draining && self.policy == DrainPolicy::Reject
+    }
+}
+
+// 이미 draining 상태로 바꾼 뒤 호출한다. outstanding이 0이 될 때까지 drained에서 기다린다.
+pub(crate) fn wait_drained<'a, S>(
+    mut guard: MutexGuard<'a, S>,
+    drained: &Condvar,
+    drain: impl Fn(&S) -> &DrainState,
+    timeout: Option<Duration>,
+) -> Result<(), AcquireError> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    while drain(&guard).outstanding > 0 {
+        guard = match deadline {
+            None => drained.wait(guard).unwrap(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(AcquireError::Timeout);
+                }
+                drained.wait_timeout(guard, deadline - now).unwrap().0
+            }
+        };
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcquireError {
+    Timeout,
+    WouldBlock,
+    Rejected,
+    Shed,
+    Draining,
+}
+
+impl fmt::Display for AcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AcquireError::Timeout => write!(f, "timed out waiting for a permit"),
+            AcquireError::WouldBlock => write!(f, "no permit available"),
+            AcquireError::Rejected => write!(f, "too many waiters, request rejected"),
+            AcquireError::Shed => write!(f, "waiter shed by queue management"),
+            AcquireError::Draining => write!(f, "semaphore is draining"),
+        }
+    }
+}
+
+impl std::error::Error for AcquireError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseError {
+    NotOwner,
+    NotHeld,
+    // 나간 permit이 없는 release로 permit을 늘리려 했지만 이미 상한에 닿았다.
+    ExceedsMax { max: usize },
+}
+
+impl fmt::Display for ReleaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReleaseError::NotOwner => write!(f, "permit is held by another thread"),
+            ReleaseError::NotHeld => write!(f, "permit is not held"),
+            ReleaseError::ExceedsMax { max } => write!(f, "release would exceed max permits ({})", max),
+        }
+    }
+}
+
+impl std::error::Error for ReleaseError {}
@@ -0,0 +1,273 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use super::error::AcquireError;
+use super::watchdog::{HoldSlot, Watchdog};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenantConfig {
+    pub weight: usize,
+    pub max_in_flight: Option<usize>,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self { weight: 1, max_in_flight: None }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantStats {
+    pub in_flight: usize,
+    pub waiting: usize,
+    pub acquired: u64,
+    pub timed_out: u64,
+    pub total_wait: Duration,
+}
+
+struct Waiter {
+    cvar: Condvar,
+    granted: Mutex<bool>,
+    enqueued: Instant,
+}
+
+struct Tenant {
+    config: TenantConfig,
+    // configure_tenant로 등록된 tenant만 쉬는 동안에도 남겨 둔다.
+    configured: bool,
+    deficit: usize,
+    queue: VecDeque<Arc<Waiter>>,
+    stats: TenantStats,
+}
+
+impl Tenant {
+    fn new(config: TenantConfig) -> Self {
+        Self { config, configured: false, deficit: 0, queue: VecDeque::new(), stats: TenantStats::default() }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.queue.is_empty() && self.stats.in_flight == 0
+    }
+
+    fn at_cap(&self) -> bool {
+        self.config.max_in_flight.is_some_and(|max| self.stats.in_flight >= max)
+    }
+}
+
+struct State<K> {
+    count: usize,
+    tenants: HashMap<K, Tenant>,
+    // 대기자가 있는 tenant들의 round-robin 순서.
+    active: VecDeque<K>,
+}
+
+// tenant별 대기열을 deficit round-robin으로 돌면서 permit을 나눠 준다.
+// 한 차례에 tenant는 weight만큼 permit을 받을 수 있고, 그 다음 tenant로 넘어간다.
+pub struct FairShareSemaphore<K> {
+    state: Mutex<State<K>>,
+    holds: HoldSlot,
+}
+
+impl<K: Eq + Hash + Clone> FairShareSemaphore<K> {
+    pub fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new(State {
+                count,
+                tenants: HashMap::new(),
+                active: VecDeque::new(),
+            }),
+            holds: HoldSlot::default(),
+        }
+    }
+
+    pub fn configure_tenant(&self, key: K, config: TenantConfig) {
+        assert!(config.weight > 0, "tenant weight must be greater than zero");
+        let mut state = self.state.lock().unwrap();
+        let tenant = state.tenants.entry(key).or_insert_with(|| Tenant::new(config));
+        tenant.config = config;
+        tenant.configured = true;
+        self.dispatch(&mut state);
+    }
+
+    pub fn tenant_stats(&self, key: &K) -> Option<TenantStats> {
+        let state = self.state.lock().unwrap();
+        state.tenants.get(key).map(|tenant| TenantStats { waiting: tenant.queue.len(), ..tenant.stats })
+    }
+
+    pub fn tenant_count(&self) -> usize {
+        self.state.lock().unwrap().tenants.len()
+    }
+
+    pub fn acquire(&self, key: &K) {
+        let _ = self.acquire_inner(key, None);
+    }
+
+    pub fn acquire_timeout(&self, key: &K, timeout: Duration) -> Result<(), AcquireError> {
+        self.acquire_inner(key, Some(Instant::now() + timeout))
+    }
+
+    pub fn try_acquire(&self, key: &K) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        let free = state.count > 0;
+        let tenant = Self::tenant(&mut state, key);
+        if !Self::can_take(free, tenant) {
+            Self::forget_if_idle(&mut state, key);
+            return Err(AcquireError::WouldBlock);
+        }
+        tenant.stats.in_flight += 1;
+        tenant.stats.acquired += 1;
+        state.count -= 1;
+        self.holds.on_acquire(1);
+        Ok(())
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().count
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.tenants.values().map(|tenant| tenant.queue.len()).sum()
+    }
+
+    pub fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.holds.watch(watchdog, name)
+    }
+
+    pub fn release(&self, key: &K) {
+        self.holds.on_release(1);
+        let mut state = self.state.lock().unwrap();
+        let tenant = state.tenants.get_mut(key).expect("release for a tenant that never acquired");
+        assert!(tenant.stats.in_flight > 0, "release without a matching acquire");
+        tenant.stats.in_flight -= 1;
+        state.count += 1;
+        self.dispatch(&mut state);
+        Self::forget_if_idle(&mut state, key);
+    }
+
+    // 상태가 바뀔 때마다 dispatch를 돌리므로, permit이 남아 있다면 대기 중인 tenant는 모두 cap에 걸려 있다.
+    // 따라서 다른 tenant의 대기자 때문에 이 tenant의 fast path를 막을 필요는 없다.
+    fn can_take(free: bool, tenant: &Tenant) -> bool {
+        free && tenant.queue.is_empty() && !tenant.at_cap()
+    }
+
+    fn tenant<'a>(state: &'a mut State<K>, key: &K) -> &'a mut Tenant {
+        state.tenants.entry(key.clone()).or_insert_with(|| Tenant::new(TenantConfig::default()))
+    }
+
+    // 설정 없이 들어온 key마다 tenant가 쌓이지 않도록, 대기자도 나간 permit도 없으면 지운다.
+    // 통계도 함께 사라지므로 tenant_stats를 계속 보려면 configure_tenant로 등록해야 한다.
+    fn forget_if_idle(state: &mut State<K>, key: &K) {
+        if state.tenants.get(key).is_some_and(|tenant| !tenant.configured && tenant.is_idle()) {
+            state.tenants.remove(key);
+        }
+    }
+
+    fn acquire_inner(&self, key: &K, deadline: Option<Instant>) -> Result<(), AcquireError> {
+        let result = self.wait_for_permit(key, deadline);
+        if result.is_ok() {
+            self.holds.on_acquire(1);
+        }
+        result
+    }
+
+    fn wait_for_permit(&self, key: &K, deadline: Option<Instant>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        let free = state.count > 0;
+        let tenant = Self::tenant(&mut state, key);
+        if Self::can_take(free, tenant) {
+            tenant.stats.in_flight += 1;
+            tenant.stats.acquired += 1;
+            state.count -= 1;
+            return Ok(());
+        }
+
+        let waiter = Arc::new(Waiter {
+            cvar: Condvar::new(),
+            granted: Mutex::new(false),
+            enqueued: Instant::now(),
+        });
+        tenant.queue.push_back(Arc::clone(&waiter));
+        if !state.active.contains(key) {
+            state.active.push_back(key.clone());
+        }
+        self.dispatch(&mut state);
+
+        while !*waiter.granted.lock().unwrap() {
+            state = match deadline {
+                None => waiter.cvar.wait(state).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        let tenant = Self::tenant(&mut state, key);
+                        tenant.queue.retain(|w| !Arc::ptr_eq(w, &waiter));
+                        tenant.stats.timed_out += 1;
+                        if tenant.queue.is_empty() {
+                            tenant.deficit = 0;
+                            state.active.retain(|k| k != key);
+                        }
+                        self.dispatch(&mut state);
+                        Self::forget_if_idle(&mut state, key);
+                        return Err(AcquireError::Timeout);
+                    }
+                    waiter.cvar.wait_timeout(state, deadline - now).unwrap().0
+                }
+            };
+        }
+        Ok(())
+    }
+
+    fn dispatch(&self, state: &mut State<K>) {
+        // 모든 tenant가 cap에 걸려 한 바퀴를 헛돌면 멈춘다.
+        let mut skipped = 0;
+        while state.count > 0 && skipped < state.active.len() {
+            let key = state.active.front().unwrap().clone();
+            let tenant = state.tenants.get_mut(&key).unwrap();
+
+            if tenant.queue.is_empty() {
+                tenant.deficit = 0;
+                state.active.pop_front();
+                continue;
+            }
+            if tenant.at_cap() {
+                state.active.rotate_left(1);
+                skipped += 1;
+                continue;
+            }
+            if tenant.deficit == 0 {
+                tenant.deficit = tenant.config.weight;
+            }
+
+            let waiter = tenant.queue.pop_front().unwrap();
+            tenant.deficit -= 1;
+            tenant.stats.in_flight += 1;
+            tenant.stats.acquired += 1;
+            tenant.stats.total_wait += waiter.enqueued.elapsed();
+            *waiter.granted.lock().unwrap() = true;
+            waiter.cvar.notify_one();
+            state.count -= 1;
+            skipped = 0;
+
+            if tenant.queue.is_empty() {
+                tenant.deficit = 0;
+                state.active.pop_front();
+            } else if tenant.deficit == 0 {
+                state.active.rotate_left(1);
+            }
+        }
+    }
+}
+
+impl<K> fmt::Debug for FairShareSemaphore<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("FairShareSemaphore")
+            .field("available_permits", &state.count)
+            .field("waiting_threads", &state.tenants.values().map(|tenant| tenant.queue.len()).sum::<usize>())
+            .field("tenants", &state.tenants.len())
+            .field("active_tenants", &state.active.len())
+            .finish()
+    }
+}
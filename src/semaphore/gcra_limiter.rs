@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use super::clock::{Clock, SystemClock};
+use super::error::AcquireError;
+use super::permit::{Limiter, Permit};
+
+// Generic Cell Rate Algorithm. 상태는 이론적 도착 시각(TAT) 하나뿐이라
+// 토큰을 채워 줄 스레드가 필요 없다. 시각은 clock 기준 나노초로 저장한다.
+#[derive(Clone, Copy)]
+struct Gcra {
+    interval: u64,
+    burst: u64,
+}
+
+impl Gcra {
+    fn new(rate_per_second: u32, burst: usize) -> Self {
+        assert!(rate_per_second > 0, "rate must be greater than zero");
+        assert!(burst > 0, "burst must be greater than zero");
+        Self {
+            // 초당 10억 개를 넘으면 간격이 0ns로 잘리므로 1ns로 묶는다.
+            interval: (1_000_000_000 / rate_per_second as u64).max(1),
+            burst: burst as u64,
+        }
+    }
+
+    fn check_cost(&self, cost: usize) {
+        assert!(cost as u64 <= self.burst, "cost {} exceeds burst size {}", cost, self.burst);
+    }
+
+    // 허용되면 새 TAT를, 아니면 허용될 때까지 기다려야 하는 나노초를 돌려준다.
+    fn next_tat(&self, tat: u64, now: u64, cost: usize) -> Result<u64, u64> {
+        let new_tat = tat.max(now) + self.interval * cost as u64;
+        let limit = now + self.interval * self.burst;
+        if new_tat <= limit {
+            Ok(new_tat)
+        } else {
+            Err(new_tat - limit)
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+pub struct GcraLimiter<C: Clock = SystemClock> {
+    gcra: Gcra,
+    tat: AtomicU64,
+    clock: C,
+}
+
+impl GcraLimiter<SystemClock> {
+    pub fn new(rate_per_second: u32, burst: usize) -> Self {
+        Self::with_clock(rate_per_second, burst, SystemClock::new())
+    }
+}
+
+impl<C: Clock> GcraLimiter<C> {
+    pub fn with_clock(rate_per_second: u32, burst: usize, clock: C) -> Self {
+        Self {
+            gcra: Gcra::new(rate_per_second, burst),
+            tat: AtomicU64::new(0),
+            clock,
+        }
+    }
+
+    pub fn acquire(&self, cost: usize) {
+        self.gcra.check_cost(cost);
+        while let Err(wait) = self.try_take(cost) {
+            self.clock.sleep(wait);
+        }
+    }
+
+    pub fn try_acquire(&self, cost: usize) -> Result<(), AcquireError> {
+        self.gcra.check_cost(cost);
+        self.try_take(cost).map_err(|_| AcquireError::WouldBlock)
+    }
+
+    pub fn acquire_timeout(&self, cost: usize, timeout: Duration) -> Result<(), AcquireError> {
+        self.gcra.check_cost(cost);
+        let deadline = self.clock.now() + timeout;
+        loop {
+            let wait = match self.try_take(cost) {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            };
+            if self.clock.now() + wait > deadline {
+                return Err(AcquireError::Timeout);
+            }
+            self.clock.sleep(wait);
+        }
+    }
+
+    fn try_take(&self, cost: usize) -> Result<(), Duration> {
+        let now = nanos(self.clock.now());
+        let mut tat = self.tat.load(Ordering::SeqCst);
+        loop {
+            let new_tat = self.gcra.next_tat(tat, now, cost).map_err(Duration::from_nanos)?;
+            match self.tat.compare_exchange_weak(tat, new_tat, Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return Ok(()),
+                Err(actual) => tat = actual,
+            }
+        }
+    }
+}
+
+impl<C: Clock> Limiter for GcraLimiter<C> {
+    fn acquire_permit(&self, cost: usize) -> Permit<'_> {
+        self.acquire(cost);
+        Permit::consumed(cost)
+    }
+
+    fn try_acquire_permit(&self, cost: usize) -> Result<Permit<'_>, AcquireError> {
+        self.try_acquire(cost)?;
+        Ok(Permit::consumed(cost))
+    }
+
+    fn acquire_permit_timeout(&self, cost: usize, timeout: Duration) -> Result<Permit<'_>, AcquireError> {
+        self.acquire_timeout(cost, timeout)?;
+        Ok(Permit::consumed(cost))
+    }
+}
+
+// 키마다 독립된 TAT를 가진다. TAT가 이미 지난 키는 새 키와 구별되지 않으므로 cleanup으로 지워도 된다.
+pub struct KeyedGcraLimiter<K, C: Clock = SystemClock> {
+    gcra: Gcra,
+    tats: Mutex<HashMap<K, u64>>,
+    clock: C,
+}
+
+impl<K: Eq + Hash + Clone> KeyedGcraLimiter<K, SystemClock> {
+    pub fn new(rate_per_second: u32, burst: usize) -> Self {
+        Self::with_clock(rate_per_second, burst, SystemClock::new())
+    }
+}
+
+impl<K: Eq + Hash + Clone, C: Clock> KeyedGcraLimiter<K, C> {
+    pub fn with_clock(rate_per_second: u32, burst: usize, clock: C) -> Self {
+        Self {
+            gcra: Gcra::new(rate_per_second, burst),
+            tats: Mutex::new(HashMap::new()),
+            clock,
+        }
+    }
+
+    pub fn acquire(&self, key: &K, cost: usize) {
+        self.gcra.check_cost(cost);
+        while let Err(wait) = self.try_take(key, cost) {
+            self.clock.sleep(wait);
+        }
+    }
+
+    pub fn try_acquire(&self, key: &K, cost: usize) -> Result<(), AcquireError> {
+        self.gcra.check_cost(cost);
+        self.try_take(key, cost).map_err(|_| AcquireError::WouldBlock)
+    }
+
+    pub fn acquire_timeout(&self, key: &K, cost: usize, timeout: Duration) -> Result<(), AcquireError> {
+        self.gcra.check_cost(cost);
+        let deadline = self.clock.now() + timeout;
+        loop {
+            let wait = match self.try_take(key, cost) {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            };
+            if self.clock.now() + wait > deadline {
+                return Err(AcquireError::Timeout);
+            }
+            self.clock.sleep(wait);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tats.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn cleanup(&self) {
+        let now = nanos(self.clock.now());
+        self.tats.lock().unwrap().retain(|_, tat| *tat > now);
+    }
+
+    fn try_take(&self, key: &K, cost: usize) -> Result<(), Duration> {
+        let now = nanos(self.clock.now());
+        let mut tats = self.tats.lock().unwrap();
+        let tat = tats.get(key).copied().unwrap_or(0);
+        let new_tat = self.gcra.next_tat(tat, now, cost).map_err(Duration::from_nanos)?;
+        tats.insert(key.clone(), new_tat);
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+// 자식 permit 하나는 자신부터 root까지 모든 조상의 permit을 하나씩 소비한다.
+// 항상 깊은 쪽(leaf)에서 root 방향으로 잡으므로 잡는 순서가 트리 깊이로 고정되어 교착이 생기지 않고,
+// 포화된 자식의 대기자가 조상의 permit을 쥔 채 기다리며 형제들을 굶기는 일도 없다.
+pub struct HierarchicalSemaphore {
+    semaphore: CountingSemaphore,
+    limit: usize,
+    parent: Option<Arc<HierarchicalSemaphore>>,
+}
+
+impl HierarchicalSemaphore {
+    pub fn new(limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            semaphore: CountingSemaphore::untracked(limit),
+            limit,
+            parent: None,
+        })
+    }
+
+    pub fn child(self: &Arc<Self>, limit: usize) -> Arc<Self> {
+        Arc::new(Self {
+            semaphore: CountingSemaphore::untracked(limit),
+            limit,
+            parent: Some(Arc::clone(self)),
+        })
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn parent(&self) -> Option<&Arc<HierarchicalSemaphore>> {
+        self.parent.as_ref()
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    // 이 노드의 permit을 기다리는 스레드 수. 자식을 통해 들어와 이 노드에서 막힌 스레드도 포함된다.
+    pub fn waiting_threads(&self) -> usize {
+        self.semaphore.waiting_threads()
+    }
+
+    pub fn acquire(&self) {
+        for node in self.path() {
+            node.semaphore.acquire();
+        }
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        self.acquire_with(|semaphore| semaphore.try_acquire())
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        let deadline = Instant::now() + timeout;
+        self.acquire_with(|semaphore| {
+            semaphore.acquire_timeout(deadline.saturating_duration_since(Instant::now()))
+        })
+    }
+
+    pub fn release(&self) {
+        for node in self.path() {
+            node.semaphore.release();
+        }
+    }
+
+    fn acquire_with<F>(&self, acquire: F) -> Result<(), AcquireError>
+    where
+        F: Fn(&CountingSemaphore) -> Result<(), AcquireError>,
+    {
+        let path: Vec<_> = self.path().collect();
+        for (i, node) in path.iter().enumerate() {
+            if let Err(err) = acquire(&node.semaphore) {
+                for held in &path[..i] {
+                    held.semaphore.release();
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self) -> impl Iterator<Item = &HierarchicalSemaphore> {
+        std::iter::successors(Some(self), |node| node.parent.as_deref())
+    }
+}
+
+impl fmt::Debug for HierarchicalSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HierarchicalSemaphore")
+            .field("limit", &self.limit)
+            .field("available_permits", &self.available_permits())
+            .field("waiting_threads", &self.waiting_threads())
+            .field("depth", &(self.path().count() - 1))
+            .finish()
+    }
+}
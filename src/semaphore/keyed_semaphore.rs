@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+struct Entry {
+    semaphore: Arc<CountingSemaphore>,
+    // permit을 들고 있거나 기다리는 중인 스레드 수. 0이 되면 모든 permit이 반납된 것이므로 항목을 지운다.
+    users: usize,
+}
+
+pub struct KeyedSemaphore<K> {
+    entries: Mutex<HashMap<K, Entry>>,
+    limits: Mutex<HashMap<K, usize>>,
+    default_limit: usize,
+    global: Option<CountingSemaphore>,
+}
+
+impl<K: Eq + Hash + Clone> KeyedSemaphore<K> {
+    pub fn new(default_limit: usize) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            limits: Mutex::new(HashMap::new()),
+            default_limit,
+            global: None,
+        }
+    }
+
+    pub fn with_global_limit(default_limit: usize, global_limit: usize) -> Self {
+        Self { global: Some(CountingSemaphore::untracked(global_limit)), ..Self::new(default_limit) }
+    }
+
+    // 이미 사용 중인 키에는 그 항목이 정리된 뒤부터 적용된다.
+    pub fn set_limit(&self, key: K, limit: usize) {
+        self.limits.lock().unwrap().insert(key, limit);
+    }
+
+    pub fn limit(&self, key: &K) -> usize {
+        self.limits.lock().unwrap().get(key).copied().unwrap_or(self.default_limit)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn available_permits(&self, key: &K) -> usize {
+        match self.entries.lock().unwrap().get(key) {
+            Some(entry) => entry.semaphore.available_permits(),
+            None => self.limit(key),
+        }
+    }
+
+    pub fn waiting_threads(&self, key: &K) -> usize {
+        match self.entries.lock().unwrap().get(key) {
+            Some(entry) => entry.semaphore.waiting_threads(),
+            None => 0,
+        }
+    }
+
+    pub fn global_available_permits(&self) -> Option<usize> {
+        self.global.as_ref().map(CountingSemaphore::available_permits)
+    }
+
+    pub fn acquire(&self, key: &K) -> KeyedPermit<'_, K> {
+        let semaphore = self.checkout(key);
+        semaphore.acquire();
+        if let Some(global) = &self.global {
+            global.acquire();
+        }
+        KeyedPermit { owner: self, key: key.clone(), semaphore }
+    }
+
+    pub fn try_acquire(&self, key: &K) -> Result<KeyedPermit<'_, K>, AcquireError> {
+        self.acquire_with(key, |semaphore| semaphore.try_acquire())
+    }
+
+    pub fn acquire_timeout(&self, key: &K, timeout: Duration) -> Result<KeyedPermit<'_, K>, AcquireError> {
+        let deadline = Instant::now() + timeout;
+        self.acquire_with(key, |semaphore| {
+            semaphore.acquire_timeout(deadline.saturating_duration_since(Instant::now()))
+        })
+    }
+
+    // 키 semaphore를 먼저, global semaphore를 나중에 잡는 순서를 항상 지켜서 교착을 피한다.
+    fn acquire_with<F>(&self, key: &K, acquire: F) -> Result<KeyedPermit<'_, K>, AcquireError>
+    where
+        F: Fn(&CountingSemaphore) -> Result<(), AcquireError>,
+    {
+        let semaphore = self.checkout(key);
+        if let Err(err) = acquire(&semaphore) {
+            self.checkin(key);
+            return Err(err);
+        }
+        if let Some(global) = &self.global {
+            if let Err(err) = acquire(global) {
+                semaphore.release();
+                self.checkin(key);
+                return Err(err);
+            }
+        }
+        Ok(KeyedPermit { owner: self, key: key.clone(), semaphore })
+    }
+
+    fn checkout(&self, key: &K) -> Arc<CountingSemaphore> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.entry(key.clone()).or_insert_with(|| Entry {
+            semaphore: Arc::new(CountingSemaphore::untracked(self.limit(key))),
+            users: 0,
+        });
+        entry.users += 1;
+        Arc::clone(&entry.semaphore)
+    }
+
+    fn checkin(&self, key: &K) {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(key).unwrap();
+        entry.users -= 1;
+        if entry.users == 0 {
+            entries.remove(key);
+        }
+    }
+}
+
+// 키마다 따로 세지 않고, 모든 키와 global semaphore에서 기다리는 스레드를 합쳐서 보여준다.
+impl<K> fmt::Debug for KeyedSemaphore<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.lock().unwrap();
+        let waiting: usize = entries.values().map(|entry| entry.semaphore.waiting_threads()).sum::<usize>()
+            + self.global.as_ref().map_or(0, CountingSemaphore::waiting_threads);
+        f.debug_struct("KeyedSemaphore")
+            .field("keys", &entries.len())
+            .field("default_limit", &self.default_limit)
+            .field("waiting_threads", &waiting)
+            .field("global_available_permits", &self.global.as_ref().map(CountingSemaphore::available_permits))
+            .finish()
+    }
+}
+
+pub struct KeyedPermit<'a, K: Eq + Hash + Clone> {
+    owner: &'a KeyedSemaphore<K>,
+    key: K,
+    semaphore: Arc<CountingSemaphore>,
+}
+
+impl<K: Eq + Hash + Clone> KeyedPermit<'_, K> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<K: Eq + Hash + Clone> Drop for KeyedPermit<'_, K> {
+    fn drop(&mut self) {
+        if let Some(global) = &self.owner.global {
+            global.release();
+        }
+        self.semaphore.release();
+        self.owner.checkin(&self.key);
+    }
+}
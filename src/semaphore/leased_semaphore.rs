@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use super::clock::{Clock, SystemClock};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaseExpired;
+
+impl fmt::Display for LeaseExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lease already expired and its permit was reclaimed")
+    }
+}
+
+impl std::error::Error for LeaseExpired {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiredLease {
+    pub id: u64,
+    pub thread_name: Option<String>,
+    pub acquired_at: Duration,
+    pub expired_at: Duration,
+}
+
+struct LeaseInfo {
+    thread_name: Option<String>,
+    acquired_at: Duration,
+    expires_at: Duration,
+}
+
+// 각 permit에 만료 시각을 붙여 둔다. 보유자가 멈춰서 반납하지 못하더라도 reap이 만료된 permit을 되찾는다.
+pub struct LeasedSemaphore<C: Clock = SystemClock> {
+    semaphore: CountingSemaphore,
+    leases: Mutex<HashMap<u64, LeaseInfo>>,
+    next_id: Mutex<u64>,
+    late_releases: AtomicUsize,
+    clock: C,
+}
+
+impl LeasedSemaphore<SystemClock> {
+    pub fn new(count: usize) -> Self {
+        Self::with_clock(count, SystemClock::new())
+    }
+}
+
+impl<C: Clock> LeasedSemaphore<C> {
+    pub fn with_clock(count: usize, clock: C) -> Self {
+        Self {
+            semaphore: CountingSemaphore::untracked(count),
+            leases: Mutex::new(HashMap::new()),
+            next_id: Mutex::new(0),
+            late_releases: AtomicUsize::new(0),
+            clock,
+        }
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    pub fn active_leases(&self) -> usize {
+        self.leases.lock().unwrap().len()
+    }
+
+    pub fn late_releases(&self) -> usize {
+        self.late_releases.load(Ordering::SeqCst)
+    }
+
+    pub fn acquire(&self, ttl: Duration) -> Lease<'_, C> {
+        self.semaphore.acquire();
+        self.grant(ttl)
+    }
+
+    // permit이 없으면 만료된 lease부터 회수해 보고 다시 시도한다.
+    pub fn try_acquire(&self, ttl: Duration) -> Result<Lease<'_, C>, AcquireError> {
+        if self.semaphore.try_acquire().is_err() {
+            self.reap();
+            self.semaphore.try_acquire()?;
+        }
+        Ok(self.grant(ttl))
+    }
+
+    pub fn acquire_timeout(&self, ttl: Duration, timeout: Duration) -> Result<Lease<'_, C>, AcquireError> {
+        if self.semaphore.try_acquire().is_err() {
+            self.reap();
+            self.semaphore.acquire_timeout(timeout)?;
+        }
+        Ok(self.grant(ttl))
+    }
+
+    pub fn reap(&self) -> Vec<ExpiredLease> {
+        let expired = self.take_expired();
+        self.semaphore.release_many(expired.len());
+        expired
+    }
+
+    // 만료된 lease를 목록에서만 빼낸다. permit은 호출자가 돌려준다.
+    fn take_expired(&self) -> Vec<ExpiredLease> {
+        let now = self.clock.now();
+        let mut leases = self.leases.lock().unwrap();
+        let expired_ids: Vec<u64> = leases
+            .iter()
+            .filter(|(_, info)| info.expires_at <= now)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut expired: Vec<ExpiredLease> = expired_ids
+            .into_iter()
+            .map(|id| {
+                let info = leases.remove(&id).unwrap();
+                ExpiredLease {
+                    id,
+                    thread_name: info.thread_name,
+                    acquired_at: info.acquired_at,
+                    expired_at: info.expires_at,
+                }
+            })
+            .collect();
+        drop(leases);
+
+        expired.sort_by_key(|lease| lease.id);
+        expired
+    }
+
+    fn grant(&self, ttl: Duration) -> Lease<'_, C> {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            *next_id
+        };
+        let now = self.clock.now();
+        self.leases.lock().unwrap().insert(id, LeaseInfo {
+            thread_name: thread::current().name().map(str::to_owned),
+            acquired_at: now,
+            expires_at: now + ttl,
+        });
+        Lease { owner: self, id, released: false }
+    }
+
+    fn renew(&self, id: u64, ttl: Duration) -> Result<(), LeaseExpired> {
+        let now = self.clock.now();
+        let mut leases = self.leases.lock().unwrap();
+        match leases.get_mut(&id) {
+            Some(info) if info.expires_at > now => {
+                info.expires_at = now + ttl;
+                Ok(())
+            }
+            _ => Err(LeaseExpired),
+        }
+    }
+
+    // 이미 reap된 lease의 반납은 permit을 두 번 돌려주지 않도록 무시한다.
+    // 만료됐지만 아직 reap되지 않았다면 permit은 한 번만 돌려주되 늦은 반납으로 센다.
+    fn release(&self, id: u64) -> Result<(), LeaseExpired> {
+        let info = self.leases.lock().unwrap().remove(&id);
+        let late = match info {
+            None => {
+                self.late_releases.fetch_add(1, Ordering::SeqCst);
+                return Err(LeaseExpired);
+            }
+            Some(info) => info.expires_at <= self.clock.now(),
+        };
+        self.semaphore.release();
+        if late {
+            self.late_releases.fetch_add(1, Ordering::SeqCst);
+            return Err(LeaseExpired);
+        }
+        Ok(())
+    }
+}
+
+impl<C: Clock + 'static> LeasedSemaphore<C> {
+    pub fn spawn_reaper<F>(self: &Arc<Self>, interval: Duration, on_expired: F) -> ReaperHandle
+    where
+        F: Fn(&ExpiredLease) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let semaphore = Arc::clone(self);
+            let stop = Arc::clone(&stop);
+            thread::Builder::new()
+                .name("lease-reaper".into())
+                .spawn(move || {
+                    while !stop.load(Ordering::SeqCst) {
+                        // 보고를 마친 뒤에 permit을 돌려주므로, 되찾은 permit을 얻은 쪽에서는 보고가 이미 끝나 있다.
+                        let expired = semaphore.take_expired();
+                        for lease in &expired {
+                            on_expired(lease);
+                        }
+                        semaphore.semaphore.release_many(expired.len());
+                        thread::park_timeout(interval);
+                    }
+                })
+                .unwrap()
+        };
+        ReaperHandle { stop, handle: Some(handle) }
+    }
+}
+
+pub struct Lease<'a, C: Clock = SystemClock> {
+    owner: &'a LeasedSemaphore<C>,
+    id: u64,
+    released: bool,
+}
+
+impl<C: Clock> Lease<'_, C> {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn renew(&self, ttl: Duration) -> Result<(), LeaseExpired> {
+        self.owner.renew(self.id, ttl)
+    }
+
+    pub fn release(mut self) -> Result<(), LeaseExpired> {
+        self.released = true;
+        self.owner.release(self.id)
+    }
+}
+
+impl<C: Clock> Drop for Lease<'_, C> {
+    fn drop(&mut self) {
+        if !self.released {
+            let _ = self.owner.release(self.id);
+        }
+    }
+}
+
+pub struct ReaperHandle {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ReaperHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
@@ -1,4 +1,32 @@
+pub mod barrier;
 pub mod binary_semaphore;
+pub mod bounded_queue;
+pub mod builder;
+pub mod bulkhead;
+pub mod clock;
+pub mod concurrency_limiter;
+pub mod config;
+pub mod countdown_latch;
 pub mod counting_semaphore;
+pub mod drain;
+pub mod error;
+pub mod fair_share_semaphore;
+pub mod gcra_limiter;
+pub mod hierarchical_semaphore;
+pub mod keyed_semaphore;
+pub mod leased_semaphore;
+pub mod multi_acquire;
+pub mod object_pool;
+pub mod ownership;
+pub mod pause;
+pub mod permit;
+pub mod phaser;
+pub mod priority_semaphore;
+pub mod rate_limiter;
+pub mod reentrant_semaphore;
+pub mod registry;
+pub mod select;
+pub mod sem_mutex;
 pub mod strong_semaphore;
-pub mod weak_semaphore;
\ No newline at end of file
+pub mod watchdog;
+pub mod weak_semaphore;
@@ -0,0 +1,61 @@
+use std::time::{Duration, Instant};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+// 모든 호출자가 같은 순서(주소 순)로 잡으므로 서로를 기다리며 교착에 빠지지 않는다.
+// 하나라도 실패하면 이미 잡은 permit을 모두 되돌려서, 호출자에게는 전부 아니면 전무로 보인다.
+// 같은 세마포어가 여러 번 나오면 하나로 묶어 한 번에 잡는다. 하나씩 잡으면 일부를 쥔 채 자기 자신을 기다리게 된다.
+fn ordered<'a>(semaphores: &[&'a CountingSemaphore]) -> Vec<(&'a CountingSemaphore, usize)> {
+    let mut sorted = semaphores.to_vec();
+    sorted.sort_by_key(|s| *s as *const CountingSemaphore as usize);
+    let mut ordered: Vec<(&CountingSemaphore, usize)> = Vec::new();
+    for semaphore in sorted {
+        match ordered.last_mut() {
+            Some((last, n)) if std::ptr::eq(*last, semaphore) => *n += 1,
+            _ => ordered.push((semaphore, 1)),
+        }
+    }
+    ordered
+}
+
+fn rollback(acquired: &[(&CountingSemaphore, usize)]) {
+    for (semaphore, n) in acquired.iter().rev() {
+        semaphore.release_many(*n);
+    }
+}
+
+pub fn acquire_all(semaphores: &[&CountingSemaphore]) {
+    for (semaphore, n) in ordered(semaphores) {
+        semaphore.acquire_many(n);
+    }
+}
+
+pub fn try_acquire_all(semaphores: &[&CountingSemaphore]) -> Result<(), AcquireError> {
+    let ordered = ordered(semaphores);
+    for (i, (semaphore, n)) in ordered.iter().enumerate() {
+        if let Err(err) = semaphore.try_acquire_many(*n) {
+            rollback(&ordered[..i]);
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+pub fn acquire_all_timeout(semaphores: &[&CountingSemaphore], timeout: Duration) -> Result<(), AcquireError> {
+    let deadline = Instant::now() + timeout;
+    let ordered = ordered(semaphores);
+    for (i, (semaphore, n)) in ordered.iter().enumerate() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if let Err(err) = semaphore.acquire_many_timeout(*n, remaining) {
+            rollback(&ordered[..i]);
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+pub fn release_all(semaphores: &[&CountingSemaphore]) {
+    for semaphore in semaphores {
+        semaphore.release();
+    }
+}
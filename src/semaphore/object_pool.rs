@@ -0,0 +1,179 @@
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+type Factory<T> = Box<dyn Fn() -> T + Send + Sync>;
+type Validator<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+struct Idle<T> {
+    object: T,
+    since: Instant,
+}
+
+// 세마포어 permit 하나가 대여 중인 객체 하나에 대응한다. 객체는 필요할 때 factory로 만들고,
+// 반납된 객체는 idle 목록에 두었다가 가장 최근 것부터 재사용한다.
+pub struct ObjectPool<T> {
+    semaphore: CountingSemaphore,
+    max_size: usize,
+    idle: Mutex<Vec<Idle<T>>>,
+    factory: Factory<T>,
+    validate_on_checkout: Option<Validator<T>>,
+    validate_on_return: Option<Validator<T>>,
+    idle_timeout: Option<Duration>,
+}
+
+impl<T> ObjectPool<T> {
+    pub fn new<F>(max_size: usize, factory: F) -> Self
+    where
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        Self {
+            semaphore: CountingSemaphore::untracked(max_size),
+            max_size,
+            idle: Mutex::new(Vec::new()),
+            factory: Box::new(factory),
+            validate_on_checkout: None,
+            validate_on_return: None,
+            idle_timeout: None,
+        }
+    }
+
+    pub fn with_checkout_validation<F>(mut self, validate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.validate_on_checkout = Some(Box::new(validate));
+        self
+    }
+
+    pub fn with_return_validation<F>(mut self, validate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.validate_on_return = Some(Box::new(validate));
+        self
+    }
+
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn idle_count(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    pub fn get(&self) -> PooledObject<'_, T> {
+        self.semaphore.acquire();
+        self.checkout()
+    }
+
+    pub fn try_get(&self) -> Result<PooledObject<'_, T>, AcquireError> {
+        self.semaphore.try_acquire()?;
+        Ok(self.checkout())
+    }
+
+    pub fn get_timeout(&self, timeout: Duration) -> Result<PooledObject<'_, T>, AcquireError> {
+        self.semaphore.acquire_timeout(timeout)?;
+        Ok(self.checkout())
+    }
+
+    // idle_timeout보다 오래 쉰 객체를 버리고, 버린 개수를 돌려준다.
+    pub fn evict_idle(&self) -> usize {
+        let idle_timeout = match self.idle_timeout {
+            Some(idle_timeout) => idle_timeout,
+            None => return 0,
+        };
+        let mut idle = self.idle.lock().unwrap();
+        let before = idle.len();
+        idle.retain(|entry| entry.since.elapsed() < idle_timeout);
+        before - idle.len()
+    }
+
+    fn is_expired(&self, entry: &Idle<T>) -> bool {
+        self.idle_timeout.is_some_and(|timeout| entry.since.elapsed() >= timeout)
+    }
+
+    // permit을 이미 얻은 상태에서 호출된다. factory나 validator가 panic하면 permit을 돌려준다.
+    fn checkout(&self) -> PooledObject<'_, T> {
+        let permit = ReleaseOnDrop(&self.semaphore);
+        let object = loop {
+            // match 조건에 lock을 두면 factory가 끝날 때까지 풀리지 않으므로 따로 꺼낸다.
+            let entry = self.idle.lock().unwrap().pop();
+            let entry = match entry {
+                Some(entry) => entry,
+                None => break (self.factory)(),
+            };
+            if self.is_expired(&entry) {
+                continue;
+            }
+            if self.validate_on_checkout.as_ref().is_none_or(|validate| validate(&entry.object)) {
+                break entry.object;
+            }
+        };
+        mem::forget(permit);
+        PooledObject { pool: self, object: Some(object) }
+    }
+
+    fn checkin(&self, object: T) {
+        let _permit = ReleaseOnDrop(&self.semaphore);
+        if self.validate_on_return.as_ref().is_none_or(|validate| validate(&object)) {
+            self.idle.lock().unwrap().push(Idle { object, since: Instant::now() });
+        }
+    }
+}
+
+// 사용자 코드를 부르는 동안 permit을 쥐고 있다가, 정상 종료든 unwind든 빠져나갈 때 돌려준다.
+struct ReleaseOnDrop<'a>(&'a CountingSemaphore);
+
+impl Drop for ReleaseOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+pub struct PooledObject<'a, T> {
+    pool: &'a ObjectPool<T>,
+    object: Option<T>,
+}
+
+impl<T> PooledObject<'_, T> {
+    // 객체를 pool에 돌려주지 않고 버린다. permit은 반납되므로 다음 대여 때 새로 만들어진다.
+    pub fn discard(mut self) {
+        self.object = None;
+        self.pool.semaphore.release();
+    }
+}
+
+impl<T> Deref for PooledObject<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.object.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for PooledObject<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.object.as_mut().unwrap()
+    }
+}
+
+impl<T> Drop for PooledObject<'_, T> {
+    fn drop(&mut self) {
+        if let Some(object) = self.object.take() {
+            self.pool.checkin(object);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, ThreadId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Misuse {
+    // 어떤 스레드도 permit을 들고 있지 않은데 release했다.
+    ReleaseWithoutAcquire,
+    // 다른 스레드가 들고 있는 permit을, 아무것도 들고 있지 않은 스레드가 release했다.
+    ReleaseFromNonHolder,
+    HeldAtThreadExit { permits: usize },
+    // 상한이 있는 세마포어에서 상한을 넘겨 release했다. 넘친 permit은 버려진다.
+    ReleaseAboveMax { max: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct MisuseReport {
+    pub misuse: Misuse,
+    pub semaphore: String,
+    pub thread_id: ThreadId,
+    pub thread_name: Option<String>,
+    pub backtrace: String,
+}
+
+// 기본은 Log다. Panic은 set_reporter나 scoped_reporter로 직접 골라야 한다.
+#[derive(Clone, Default)]
+pub enum Reporter {
+    Panic,
+    #[default]
+    Log,
+    Collect(Arc<Mutex<Vec<MisuseReport>>>),
+}
+
+impl Reporter {
+    fn report(&self, report: MisuseReport, exiting: bool) {
+        match self {
+            // 스레드 종료 중(TLS 소멸자)이나 이미 unwind 중에 panic하면 프로세스가 abort되므로 그때는 로그로 대신한다.
+            Reporter::Panic if !exiting && !thread::panicking() => {
+                panic!("semaphore misuse: {:?}\n{}", report, report.backtrace)
+            }
+            Reporter::Panic | Reporter::Log => {
+                eprintln!(
+                    "semaphore misuse: {:?} on {} in thread {:?} ({:?})\n{}",
+                    report.misuse, report.semaphore, report.thread_name, report.thread_id, report.backtrace
+                );
+            }
+            Reporter::Collect(reports) => reports.lock().unwrap().push(report),
+        }
+    }
+}
+
+static REPORTER: Mutex<Option<Reporter>> = Mutex::new(None);
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_reporter(reporter: Reporter) {
+    *REPORTER.lock().unwrap() = Some(reporter);
+}
+
+thread_local! {
+    static SCOPED_REPORTER: RefCell<Option<Reporter>> = const { RefCell::new(None) };
+}
+
+// 현재 스레드에서 일어난 보고만 가로챈다. guard가 사라지면 이전 reporter로 돌아가므로 다른 테스트로 새지 않는다.
+pub fn scoped_reporter(reporter: Reporter) -> ReporterGuard {
+    let previous = SCOPED_REPORTER.with(|scoped| scoped.borrow_mut().replace(reporter));
+    ReporterGuard { previous }
+}
+
+pub struct ReporterGuard {
+    previous: Option<Reporter>,
+}
+
+impl Drop for ReporterGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        let _ = SCOPED_REPORTER.try_with(|scoped| *scoped.borrow_mut() = previous);
+    }
+}
+
+struct TrackerInner {
+    name: String,
+    holders: Mutex<HashMap<ThreadId, usize>>,
+    reporter: Option<Reporter>,
+}
+
+impl TrackerInner {
+    // tracker에 붙은 reporter, 현재 스레드의 scoped reporter, 전역 reporter, Log 순으로 고른다.
+    fn report(&self, misuse: Misuse, exiting: bool) {
+        let reporter = self
+            .reporter
+            .clone()
+            .or_else(|| SCOPED_REPORTER.try_with(|scoped| scoped.borrow().clone()).ok().flatten())
+            .or_else(|| REPORTER.lock().unwrap().clone())
+            .unwrap_or_default();
+        // 로그만 남길 때는 RUST_BACKTRACE 설정을 따른다.
+        let backtrace = match reporter {
+            Reporter::Log => Backtrace::capture(),
+            _ => Backtrace::force_capture(),
+        };
+        let current = thread::current();
+        let report = MisuseReport {
+            misuse,
+            semaphore: self.name.clone(),
+            thread_id: current.id(),
+            thread_name: current.name().map(str::to_owned),
+            backtrace: backtrace.to_string(),
+        };
+        reporter.report(report, exiting);
+    }
+}
+
+// 스레드가 종료될 때, 그 스레드가 permit을 잡았던 tracker들을 확인한다.
+struct ThreadHolds(Vec<Weak<TrackerInner>>);
+
+impl Drop for ThreadHolds {
+    fn drop(&mut self) {
+        let id = thread::current().id();
+        for tracker in self.0.drain(..).filter_map(|weak| weak.upgrade()) {
+            let held = tracker.holders.lock().unwrap().remove(&id).unwrap_or(0);
+            if held > 0 {
+                tracker.report(Misuse::HeldAtThreadExit { permits: held }, true);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static THREAD_HOLDS: RefCell<ThreadHolds> = const { RefCell::new(ThreadHolds(Vec::new())) };
+}
+
+// `checked` feature가 꺼져 있으면 new()는 아무것도 하지 않는 tracker를 만든다.
+pub struct OwnershipTracker {
+    inner: Option<Arc<TrackerInner>>,
+}
+
+impl OwnershipTracker {
+    pub fn new(kind: &str) -> Self {
+        if cfg!(feature = "checked") {
+            Self::enabled(kind)
+        } else {
+            Self::disabled()
+        }
+    }
+
+    pub fn enabled(kind: &str) -> Self {
+        Self::build(kind, None)
+    }
+
+    pub fn with_reporter(kind: &str, reporter: Reporter) -> Self {
+        Self::build(kind, Some(reporter))
+    }
+
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    fn build(kind: &str, reporter: Option<Reporter>) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        Self {
+            inner: Some(Arc::new(TrackerInner {
+                name: format!("{}#{}", kind, id),
+                holders: Mutex::new(HashMap::new()),
+                reporter,
+            })),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.inner.as_ref().map(|inner| inner.name.as_str())
+    }
+
+    pub fn held_by_current_thread(&self) -> usize {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return 0,
+        };
+        let holders = inner.holders.lock().unwrap();
+        holders.get(&thread::current().id()).copied().unwrap_or(0)
+    }
+
+    pub fn on_acquire(&self, permits: usize) {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return,
+        };
+        *inner.holders.lock().unwrap().entry(thread::current().id()).or_insert(0) += permits;
+
+        let _ = THREAD_HOLDS.try_with(|holds| {
+            let mut holds = holds.borrow_mut();
+            let weak = Arc::downgrade(inner);
+            if !holds.0.iter().any(|w| w.ptr_eq(&weak)) {
+                holds.0.push(weak);
+            }
+        });
+    }
+
+    // on_acquire/on_release로 알 수 없는 잘못된 사용을 세마포어가 직접 보고한다.
+    pub fn report(&self, misuse: Misuse) {
+        if let Some(inner) = &self.inner {
+            inner.report(misuse, false);
+        }
+    }
+
+    pub fn on_release(&self, permits: usize) {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return,
+        };
+        let id = thread::current().id();
+        for _ in 0..permits {
+            let mut holders = inner.holders.lock().unwrap();
+            if let Some(held) = holders.get_mut(&id) {
+                *held -= 1;
+                if *held == 0 {
+                    holders.remove(&id);
+                }
+                continue;
+            }
+
+            // 다른 스레드의 permit을 대신 돌려준 것으로 보고 장부를 맞춘 뒤 보고한다.
+            let misuse = match holders.keys().next().copied() {
+                None => Misuse::ReleaseWithoutAcquire,
+                Some(other) => {
+                    let held = holders.get_mut(&other).unwrap();
+                    *held -= 1;
+                    if *held == 0 {
+                        holders.remove(&other);
+                    }
+                    Misuse::ReleaseFromNonHolder
+                }
+            };
+            drop(holders);
+            inner.report(misuse, false);
+        }
+    }
+}
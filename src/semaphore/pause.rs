@@ -0,0 +1,15 @@
+// drain과 달리 나간 permit을 기다리지 않고, resume될 때까지 새 permit 발급만 멈춘다.
+#[derive(Debug, Default)]
+pub(crate) struct PauseState {
+    pub(crate) paused: bool,
+    // pause 중에 도착해서 기다려야 했던 acquire 호출 수.
+    pub(crate) blocked: usize,
+}
+
+impl PauseState {
+    pub(crate) fn on_arrival(&mut self) {
+        if self.paused {
+            self.blocked += 1;
+        }
+    }
+}
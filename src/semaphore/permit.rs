@@ -0,0 +1,81 @@
+use std::fmt;
+use std::time::Duration;
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+// permit을 돌려받는 쪽. 동시성 제한은 drop 시 permit을 돌려받고, 속도 제한은 돌려받지 않는다.
+pub trait PermitSource: Sync {
+    fn release_permits(&self, count: usize);
+}
+
+pub struct Permit<'a> {
+    source: Option<&'a dyn PermitSource>,
+    count: usize,
+}
+
+impl<'a> Permit<'a> {
+    pub fn new(source: &'a dyn PermitSource, count: usize) -> Self {
+        Self { source: Some(source), count }
+    }
+
+    // 되돌려줄 곳이 없는 permit. 속도 제한처럼 소비되고 끝나는 경우에 쓴다.
+    pub fn consumed(count: usize) -> Self {
+        Self { source: None, count }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn forget(mut self) -> usize {
+        self.source = None;
+        self.count
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if let Some(source) = self.source.take() {
+            source.release_permits(self.count);
+        }
+    }
+}
+
+impl fmt::Debug for Permit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Permit")
+            .field("count", &self.count)
+            .field("returns_on_drop", &self.source.is_some())
+            .finish()
+    }
+}
+
+// 동시성 제한과 속도 제한을 같은 자리에 바꿔 끼울 수 있도록 하는 공통 인터페이스.
+pub trait Limiter {
+    fn acquire_permit(&self, cost: usize) -> Permit<'_>;
+    fn try_acquire_permit(&self, cost: usize) -> Result<Permit<'_>, AcquireError>;
+    fn acquire_permit_timeout(&self, cost: usize, timeout: Duration) -> Result<Permit<'_>, AcquireError>;
+}
+
+impl PermitSource for CountingSemaphore {
+    fn release_permits(&self, count: usize) {
+        self.release_many(count);
+    }
+}
+
+impl Limiter for CountingSemaphore {
+    fn acquire_permit(&self, cost: usize) -> Permit<'_> {
+        self.acquire_many(cost);
+        Permit::new(self, cost)
+    }
+
+    fn try_acquire_permit(&self, cost: usize) -> Result<Permit<'_>, AcquireError> {
+        self.try_acquire_many(cost)?;
+        Ok(Permit::new(self, cost))
+    }
+
+    fn acquire_permit_timeout(&self, cost: usize, timeout: Duration) -> Result<Permit<'_>, AcquireError> {
+        self.acquire_many_timeout(cost, timeout)?;
+        Ok(Permit::new(self, cost))
+    }
+}
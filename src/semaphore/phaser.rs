@@ -0,0 +1,143 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+struct PhaserState {
+    parties: usize,
+    arrived: usize,
+    waiting: usize,
+    phase: usize,
+    // 마지막 party가 해제되면 종료된다. 종료 후에는 phase가 더 이상 진행되지 않고 아무도 기다리지 않는다.
+    terminated: bool,
+    gate: Arc<CountingSemaphore>,
+}
+
+impl PhaserState {
+    fn advance_if_complete(&mut self) {
+        if self.terminated || self.arrived < self.parties {
+            return;
+        }
+        self.gate.release_many(self.waiting);
+        self.gate = Arc::new(CountingSemaphore::untracked(0));
+        self.arrived = 0;
+        self.waiting = 0;
+        self.phase += 1;
+    }
+}
+
+pub struct Phaser {
+    state: Mutex<PhaserState>,
+}
+
+impl Phaser {
+    pub fn new(parties: usize) -> Self {
+        Self {
+            state: Mutex::new(PhaserState {
+                parties,
+                arrived: 0,
+                waiting: 0,
+                phase: 0,
+                terminated: false,
+                gate: Arc::new(CountingSemaphore::untracked(0)),
+            }),
+        }
+    }
+
+    pub fn phase(&self) -> usize {
+        self.state.lock().unwrap().phase
+    }
+
+    pub fn registered_parties(&self) -> usize {
+        self.state.lock().unwrap().parties
+    }
+
+    pub fn arrived_parties(&self) -> usize {
+        self.state.lock().unwrap().arrived
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.state.lock().unwrap().terminated
+    }
+
+    pub fn register(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        assert!(!state.terminated, "cannot register on a terminated phaser");
+        state.parties += 1;
+        state.phase
+    }
+
+    // 기다리지 않고 도착만 알린다. 반환값은 도착한 phase 번호이다.
+    pub fn arrive(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let phase = state.phase;
+        if !state.terminated {
+            state.arrived += 1;
+            state.advance_if_complete();
+        }
+        phase
+    }
+
+    pub fn arrive_and_deregister(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        assert!(state.parties > 0, "no registered parties to deregister");
+        let phase = state.phase;
+        state.parties -= 1;
+        if state.parties == 0 {
+            state.terminated = true;
+            state.gate.release_many(state.waiting);
+            state.arrived = 0;
+            state.waiting = 0;
+        } else {
+            state.advance_if_complete();
+        }
+        phase
+    }
+
+    pub fn arrive_and_wait(&self) -> usize {
+        match self.arrive_waiting() {
+            Ok(phase) => phase,
+            Err((phase, gate)) => {
+                gate.acquire();
+                phase
+            }
+        }
+    }
+
+    // 타임아웃이 나도 도착은 그대로 기록되어 있으므로 phase 진행을 막지 않는다.
+    pub fn arrive_and_wait_timeout(&self, timeout: Duration) -> Result<usize, AcquireError> {
+        let (phase, gate) = match self.arrive_waiting() {
+            Ok(phase) => return Ok(phase),
+            Err(waiting) => waiting,
+        };
+
+        if gate.acquire_timeout(timeout).is_ok() {
+            return Ok(phase);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.phase == phase && !state.terminated {
+            state.waiting -= 1;
+            return Err(AcquireError::Timeout);
+        }
+        drop(state);
+
+        gate.acquire();
+        Ok(phase)
+    }
+
+    fn arrive_waiting(&self) -> Result<usize, (usize, Arc<CountingSemaphore>)> {
+        let mut state = self.state.lock().unwrap();
+        let phase = state.phase;
+        if state.terminated {
+            return Ok(phase);
+        }
+        state.arrived += 1;
+        if state.arrived >= state.parties {
+            state.advance_if_complete();
+            return Ok(phase);
+        }
+        state.waiting += 1;
+        Err((phase, Arc::clone(&state.gate)))
+    }
+}
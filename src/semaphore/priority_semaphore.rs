@@ -0,0 +1,296 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use super::drain::{self, DrainPolicy, DrainState};
+use super::error::AcquireError;
+use super::watchdog::{HoldSlot, Watchdog};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WaiterStatus {
+    Waiting,
+    Granted,
+    Drained,
+}
+
+struct Waiter {
+    cvar: Condvar,
+    status: Mutex<WaiterStatus>,
+    enqueued: Instant,
+    // acquire/acquire_with_priority로 들어온 대기자는 실패를 돌려줄 수 없다.
+    fallible: bool,
+}
+
+// 높은 priority가 먼저, 같은 priority 안에서는 먼저 들어온(seq가 작은) 대기자가 먼저 나온다.
+struct Entry {
+    priority: u64,
+    base_priority: u32,
+    seq: u64,
+    waiter: Arc<Waiter>,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+struct State {
+    count: usize,
+    queue: BinaryHeap<Entry>,
+    next_seq: u64,
+    drain: DrainState,
+}
+
+// StrongSemaphore처럼 대기자마다 Condvar를 두고 permit을 직접 넘겨주되, 대기열을 heap으로 관리한다.
+pub struct PrioritySemaphore {
+    state: Mutex<State>,
+    drained: Condvar,
+    // 설정되면 이 시간만큼 기다릴 때마다 priority가 1씩 올라가서 낮은 priority도 결국 처리된다.
+    aging: Option<Duration>,
+    holds: HoldSlot,
+}
+
+impl PrioritySemaphore {
+    pub fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new(State {
+                count,
+                queue: BinaryHeap::new(),
+                next_seq: 0,
+                drain: DrainState::default(),
+            }),
+            drained: Condvar::new(),
+            aging: None,
+            holds: HoldSlot::default(),
+        }
+    }
+
+    pub fn with_aging(count: usize, aging: Duration) -> Self {
+        assert!(!aging.is_zero(), "aging interval must be non-zero");
+        Self { aging: Some(aging), ..Self::new(count) }
+    }
+
+    pub fn with_drain_policy(mut self, policy: DrainPolicy) -> Self {
+        self.state.get_mut().unwrap().drain.policy = policy;
+        self
+    }
+
+    pub fn acquire(&self) {
+        self.acquire_with_priority(0);
+    }
+
+    pub fn acquire_with_priority(&self, priority: u32) {
+        let _ = self.acquire_inner(priority, false, None);
+    }
+
+    pub fn acquire_with_priority_timeout(&self, priority: u32, timeout: Duration) -> Result<(), AcquireError> {
+        self.acquire_inner(priority, true, Some(Instant::now() + timeout))
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.acquire_with_priority_timeout(0, timeout)
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if state.drain.rejects() {
+            return Err(AcquireError::Draining);
+        }
+        if state.drain.draining || state.count == 0 || !state.queue.is_empty() {
+            return Err(AcquireError::WouldBlock);
+        }
+        state.count -= 1;
+        state.drain.on_grant(1);
+        self.holds.on_acquire(1);
+        Ok(())
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().count
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        self.state.lock().unwrap().queue.len()
+    }
+
+    pub fn forget_permits(&self, n: usize) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let forgotten = n.min(state.count);
+        state.count -= forgotten;
+        forgotten
+    }
+
+    pub fn forget_held_permit(&self) {
+        self.holds.on_release(1);
+        if self.state.lock().unwrap().drain.on_return(1) {
+            self.drained.notify_all();
+        }
+    }
+
+    pub fn add_permits(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.count += n;
+        self.dispatch(&mut state);
+    }
+
+    pub fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.holds.watch(watchdog, name)
+    }
+
+    pub fn release(&self) {
+        self.holds.on_release(1);
+        let mut state = self.state.lock().unwrap();
+        state.count += 1;
+        if state.drain.on_return(1) {
+            self.drained.notify_all();
+        }
+        self.dispatch(&mut state);
+    }
+
+    pub fn drain(&self) {
+        let _ = self.drain_inner(None);
+    }
+
+    pub fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.drain_inner(Some(timeout))
+    }
+
+    pub fn reopen(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = false;
+        self.dispatch(&mut state);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.state.lock().unwrap().drain.draining
+    }
+
+    fn drain_inner(&self, timeout: Option<Duration>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = true;
+        if state.drain.policy == DrainPolicy::Reject {
+            let (rejected, kept): (Vec<_>, Vec<_>) =
+                std::mem::take(&mut state.queue).into_iter().partition(|e| e.waiter.fallible);
+            state.queue = kept.into();
+            for entry in rejected {
+                *entry.waiter.status.lock().unwrap() = WaiterStatus::Drained;
+                entry.waiter.cvar.notify_one();
+            }
+        }
+        drain::wait_drained(state, &self.drained, |s| &s.drain, timeout)
+    }
+
+    fn acquire_inner(&self, priority: u32, fallible: bool, deadline: Option<Instant>) -> Result<(), AcquireError> {
+        let result = self.wait_for_permit(priority, fallible, deadline);
+        if result.is_ok() {
+            self.holds.on_acquire(1);
+        }
+        result
+    }
+
+    fn wait_for_permit(&self, priority: u32, fallible: bool, deadline: Option<Instant>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if fallible && state.drain.rejects() {
+            return Err(AcquireError::Draining);
+        }
+        if !state.drain.draining && state.count > 0 && state.queue.is_empty() {
+            state.count -= 1;
+            state.drain.on_grant(1);
+            return Ok(());
+        }
+
+        let waiter = Arc::new(Waiter {
+            cvar: Condvar::new(),
+            status: Mutex::new(WaiterStatus::Waiting),
+            enqueued: Instant::now(),
+            fallible,
+        });
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queue.push(Entry {
+            priority: priority as u64,
+            base_priority: priority,
+            seq,
+            waiter: Arc::clone(&waiter),
+        });
+
+        loop {
+            match *waiter.status.lock().unwrap() {
+                WaiterStatus::Granted => return Ok(()),
+                WaiterStatus::Drained => return Err(AcquireError::Draining),
+                WaiterStatus::Waiting => {}
+            }
+            state = match deadline {
+                None => waiter.cvar.wait(state).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        state.queue.retain(|e| !Arc::ptr_eq(&e.waiter, &waiter));
+                        return Err(AcquireError::Timeout);
+                    }
+                    waiter.cvar.wait_timeout(state, deadline - now).unwrap().0
+                }
+            };
+        }
+    }
+
+    fn dispatch(&self, state: &mut State) {
+        if state.drain.draining || state.count == 0 || state.queue.is_empty() {
+            return;
+        }
+        if let Some(aging) = self.aging {
+            // 기다린 시간에 따라 priority가 바뀌므로 heap을 다시 만든다.
+            let now = Instant::now();
+            let entries = std::mem::take(&mut state.queue).into_vec();
+            state.queue = entries
+                .into_iter()
+                .map(|mut e| {
+                    let waited = now.duration_since(e.waiter.enqueued);
+                    e.priority = e.base_priority as u64 + (waited.as_nanos() / aging.as_nanos()) as u64;
+                    e
+                })
+                .collect();
+        }
+
+        while state.count > 0 {
+            let entry = match state.queue.pop() {
+                Some(entry) => entry,
+                None => return,
+            };
+            state.count -= 1;
+            state.drain.on_grant(1);
+            *entry.waiter.status.lock().unwrap() = WaiterStatus::Granted;
+            entry.waiter.cvar.notify_one();
+        }
+    }
+}
+
+impl fmt::Debug for PrioritySemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("PrioritySemaphore")
+            .field("available_permits", &state.count)
+            .field("waiting_threads", &state.queue.len())
+            .field("aging", &self.aging)
+            .field("draining", &state.drain.draining)
+            .finish()
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use super::clock::{Clock, SystemClock};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+use super::permit::{Limiter, Permit};
+
+// 토큰은 CountingSemaphore의 permit으로 보관한다. 별도의 refill 스레드 없이,
+// 획득을 시도할 때마다 지난 시간만큼의 토큰을 burst 한도까지 채워 넣는다.
+pub struct RateLimiter<C: Clock = SystemClock> {
+    tokens: CountingSemaphore,
+    burst: usize,
+    interval: Duration,
+    last_refill: Mutex<Duration>,
+    clock: C,
+}
+
+impl RateLimiter<SystemClock> {
+    pub fn new(rate_per_second: u32, burst: usize) -> Self {
+        Self::with_clock(rate_per_second, burst, SystemClock::new())
+    }
+}
+
+impl<C: Clock> RateLimiter<C> {
+    pub fn with_clock(rate_per_second: u32, burst: usize, clock: C) -> Self {
+        assert!(rate_per_second > 0, "rate must be greater than zero");
+        assert!(burst > 0, "burst must be greater than zero");
+        let now = clock.now();
+        Self {
+            tokens: CountingSemaphore::untracked(burst),
+            burst,
+            // 초당 10억 개를 넘으면 간격이 0ns로 반올림되므로 1ns로 묶는다.
+            interval: (Duration::from_secs(1) / rate_per_second).max(Duration::from_nanos(1)),
+            last_refill: Mutex::new(now),
+            clock,
+        }
+    }
+
+    pub fn burst(&self) -> usize {
+        self.burst
+    }
+
+    pub fn available_tokens(&self) -> usize {
+        self.refill();
+        self.tokens.available_permits()
+    }
+
+    pub fn acquire(&self, cost: usize) {
+        self.check_cost(cost);
+        loop {
+            match self.try_take(cost) {
+                Ok(()) => return,
+                Err(wait) => self.clock.sleep(wait),
+            }
+        }
+    }
+
+    pub fn try_acquire(&self, cost: usize) -> Result<(), AcquireError> {
+        self.check_cost(cost);
+        self.try_take(cost).map_err(|_| AcquireError::WouldBlock)
+    }
+
+    pub fn acquire_timeout(&self, cost: usize, timeout: Duration) -> Result<(), AcquireError> {
+        self.check_cost(cost);
+        let deadline = self.clock.now() + timeout;
+        loop {
+            let wait = match self.try_take(cost) {
+                Ok(()) => return Ok(()),
+                Err(wait) => wait,
+            };
+            // 토큰은 refill로만 늘어나므로, 마감 전에 채워질 수 없다면 기다리지 않고 바로 실패한다.
+            if self.clock.now() + wait > deadline {
+                return Err(AcquireError::Timeout);
+            }
+            self.clock.sleep(wait);
+        }
+    }
+
+    fn check_cost(&self, cost: usize) {
+        assert!(cost <= self.burst, "cost {} exceeds burst size {}", cost, self.burst);
+    }
+
+    // 실패하면 cost만큼의 토큰이 채워지기까지 남은 시간을 돌려준다.
+    fn try_take(&self, cost: usize) -> Result<(), Duration> {
+        let since_refill = self.refill();
+        if self.tokens.try_acquire_many(cost).is_ok() {
+            return Ok(());
+        }
+        let missing = (cost - self.tokens.available_permits().min(cost)) as u32;
+        Err((self.interval * missing).saturating_sub(since_refill).max(Duration::from_nanos(1)))
+    }
+
+    // 새로 생긴 토큰을 채워 넣고, 마지막 refill 시점 이후 흐른 시간을 돌려준다.
+    fn refill(&self) -> Duration {
+        let mut last_refill = self.last_refill.lock().unwrap();
+        let now = self.clock.now();
+        let elapsed = now.saturating_sub(*last_refill);
+        let earned = (elapsed.as_nanos() / self.interval.as_nanos()) as usize;
+        if earned == 0 {
+            return elapsed;
+        }
+
+        let available = self.tokens.available_permits();
+        if available + earned >= self.burst {
+            *last_refill = now;
+        } else {
+            *last_refill += self.interval * earned as u32;
+        }
+        self.tokens.release_many(self.burst.saturating_sub(available).min(earned));
+        now - *last_refill
+    }
+}
+
+impl<C: Clock> Limiter for RateLimiter<C> {
+    fn acquire_permit(&self, cost: usize) -> Permit<'_> {
+        self.acquire(cost);
+        Permit::consumed(cost)
+    }
+
+    fn try_acquire_permit(&self, cost: usize) -> Result<Permit<'_>, AcquireError> {
+        self.try_acquire(cost)?;
+        Ok(Permit::consumed(cost))
+    }
+
+    fn acquire_permit_timeout(&self, cost: usize, timeout: Duration) -> Result<Permit<'_>, AcquireError> {
+        self.acquire_timeout(cost, timeout)?;
+        Ok(Permit::consumed(cost))
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+use super::binary_semaphore::BinarySemaphore;
+use super::error::{AcquireError, ReleaseError};
+
+struct Owner {
+    thread: ThreadId,
+    holds: usize,
+}
+
+// 소유 스레드가 다시 acquire하면 블록하지 않고 hold 횟수만 늘린다.
+// 마지막 release에서만 BinarySemaphore를 푼다.
+pub struct ReentrantSemaphore {
+    semaphore: BinarySemaphore,
+    owner: Mutex<Option<Owner>>,
+}
+
+impl ReentrantSemaphore {
+    pub fn new() -> Self {
+        Self {
+            semaphore: BinarySemaphore::untracked(),
+            owner: Mutex::new(None),
+        }
+    }
+
+    pub fn acquire(&self) {
+        if self.reenter() {
+            return;
+        }
+        self.semaphore.acquire();
+        self.take_ownership();
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        if self.reenter() {
+            return Ok(());
+        }
+        self.semaphore.try_acquire()?;
+        self.take_ownership();
+        Ok(())
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        if self.reenter() {
+            return Ok(());
+        }
+        self.semaphore.acquire_timeout(timeout)?;
+        self.take_ownership();
+        Ok(())
+    }
+
+    pub fn release(&self) -> Result<(), ReleaseError> {
+        let mut owner = self.owner.lock().unwrap();
+        let current = owner.as_mut().ok_or(ReleaseError::NotHeld)?;
+        if current.thread != thread::current().id() {
+            return Err(ReleaseError::NotOwner);
+        }
+        current.holds -= 1;
+        if current.holds == 0 {
+            *owner = None;
+            drop(owner);
+            self.semaphore.release();
+        }
+        Ok(())
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        self.semaphore.waiting_threads()
+    }
+
+    pub fn hold_count(&self) -> usize {
+        match &*self.owner.lock().unwrap() {
+            Some(owner) if owner.thread == thread::current().id() => owner.holds,
+            _ => 0,
+        }
+    }
+
+    pub fn is_held_by_current_thread(&self) -> bool {
+        self.hold_count() > 0
+    }
+
+    fn reenter(&self) -> bool {
+        match &mut *self.owner.lock().unwrap() {
+            Some(owner) if owner.thread == thread::current().id() => {
+                owner.holds += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn take_ownership(&self) {
+        *self.owner.lock().unwrap() = Some(Owner { thread: thread::current().id(), holds: 1 });
+    }
+}
+
+impl fmt::Debug for ReentrantSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let owner = self.owner.lock().unwrap();
+        f.debug_struct("ReentrantSemaphore")
+            .field("available_permits", &self.semaphore.available_permits())
+            .field("waiting_threads", &self.semaphore.waiting_threads())
+            .field("owner", &owner.as_ref().map(|owner| owner.thread))
+            .field("holds", &owner.as_ref().map_or(0, |owner| owner.holds))
+            .finish()
+    }
+}
+
+impl Default for ReentrantSemaphore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use super::builder::{BuildError, ConfiguredSemaphore, Fairness, Semaphore, SemaphoreBuilder};
+use super::config::{self, ConfigError, SemaphoreConfig};
+
+// 등록된 세마포어는 종류와 상관없이 permit 수를 다시 맞출 수 있어야 한다.
+trait Managed: Semaphore {
+    fn set_limits(&self, permits: usize, max: usize) -> Result<(), BuildError>;
+    fn permits(&self) -> usize;
+    fn max_permits(&self) -> usize;
+    fn held_permits(&self) -> usize;
+}
+
+impl<S: Semaphore> Managed for ConfiguredSemaphore<S> {
+    fn set_limits(&self, permits: usize, max: usize) -> Result<(), BuildError> {
+        ConfiguredSemaphore::set_limits(self, permits, max)
+    }
+
+    fn permits(&self) -> usize {
+        ConfiguredSemaphore::permits(self)
+    }
+
+    fn max_permits(&self) -> usize {
+        ConfiguredSemaphore::max_permits(self)
+    }
+
+    fn held_permits(&self) -> usize {
+        ConfiguredSemaphore::held_permits(self)
+    }
+}
+
+struct Entry {
+    kind: Fairness,
+    semaphore: Arc<dyn Managed>,
+}
+
+impl Entry {
+    fn build(config: &SemaphoreConfig) -> Result<Self, ConfigError> {
+        let builder = SemaphoreBuilder::new()
+            .name(&config.name)
+            .fairness(config.kind)
+            .initial_permits(config.permits)
+            .max_permits(config.max.unwrap_or(config.permits));
+        let semaphore: Result<Arc<dyn Managed>, BuildError> = match config.kind {
+            Fairness::Weak => builder.build_weak().map(|s| Arc::new(s) as Arc<dyn Managed>),
+            Fairness::Strong => builder.build_strong().map(|s| Arc::new(s) as Arc<dyn Managed>),
+            Fairness::Priority => builder.build_priority().map(|s| Arc::new(s) as Arc<dyn Managed>),
+        };
+        let semaphore = semaphore.map_err(|error| ConfigError::Build { name: config.name.clone(), error })?;
+        Ok(Self { kind: config.kind, semaphore })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemaphoreState {
+    pub name: String,
+    pub kind: Fairness,
+    pub permits: usize,
+    pub max_permits: usize,
+    pub available: usize,
+    pub held: usize,
+    pub waiting: usize,
+    pub draining: bool,
+}
+
+impl fmt::Display for SemaphoreState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({:?}): permits={}/{} available={} held={} waiting={}{}",
+            self.name,
+            self.kind,
+            self.permits,
+            self.max_permits,
+            self.available,
+            self.held,
+            self.waiting,
+            if self.draining { " draining" } else { "" }
+        )
+    }
+}
+
+// 이름으로 세마포어를 찾는 저장소. reload는 나가 있는 permit을 건드리지 않고 permit 수만 다시 맞춘다.
+pub struct Registry {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self { entries: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn from_configs(configs: &[SemaphoreConfig]) -> Result<Self, ConfigError> {
+        let registry = Self::new();
+        registry.apply(configs)?;
+        Ok(registry)
+    }
+
+    pub fn from_toml(input: &str) -> Result<Self, ConfigError> {
+        Self::from_configs(&config::parse_toml(input)?)
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, ConfigError> {
+        Self::from_configs(&config::parse_json(input)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_configs(&read_config(path.as_ref())?)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Semaphore>> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(name).map(|entry| Arc::clone(&entry.semaphore) as Arc<dyn Semaphore>)
+    }
+
+    pub fn register(&self, config: &SemaphoreConfig) -> Result<Arc<dyn Semaphore>, ConfigError> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.contains_key(&config.name) {
+            return Err(ConfigError::Duplicate(config.name.clone()));
+        }
+        let entry = Entry::build(config)?;
+        let semaphore = Arc::clone(&entry.semaphore) as Arc<dyn Semaphore>;
+        entries.insert(config.name.clone(), entry);
+        Ok(semaphore)
+    }
+
+    pub fn reload_toml(&self, input: &str) -> Result<(), ConfigError> {
+        self.apply(&config::parse_toml(input)?)
+    }
+
+    pub fn reload_json(&self, input: &str) -> Result<(), ConfigError> {
+        self.apply(&config::parse_json(input)?)
+    }
+
+    pub fn reload(&self, path: impl AsRef<Path>) -> Result<(), ConfigError> {
+        self.apply(&read_config(path.as_ref())?)
+    }
+
+    // 설정 전체를 먼저 검사하고 나서 적용하므로, 실패하면 아무것도 바뀌지 않는다.
+    // 설정에서 빠진 이름은 registry에서만 지워지고, 이미 꺼내 간 세마포어는 계속 쓸 수 있다.
+    pub fn apply(&self, configs: &[SemaphoreConfig]) -> Result<(), ConfigError> {
+        let mut entries = self.entries.lock().unwrap();
+        for config in configs {
+            let max = config.max.unwrap_or(config.permits);
+            if config.permits > max {
+                let error = BuildError::InitialExceedsMax { initial: config.permits, max };
+                return Err(ConfigError::Build { name: config.name.clone(), error });
+            }
+            if entries.get(&config.name).is_some_and(|entry| entry.kind != config.kind) {
+                return Err(ConfigError::KindChanged(config.name.clone()));
+            }
+        }
+
+        entries.retain(|name, _| configs.iter().any(|config| &config.name == name));
+        for config in configs {
+            let max = config.max.unwrap_or(config.permits);
+            match entries.get(&config.name) {
+                Some(entry) => entry
+                    .semaphore
+                    .set_limits(config.permits, max)
+                    .map_err(|error| ConfigError::Build { name: config.name.clone(), error })?,
+                None => {
+                    entries.insert(config.name.clone(), Entry::build(config)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn dump(&self) -> Vec<SemaphoreState> {
+        let entries = self.entries.lock().unwrap();
+        let mut states: Vec<SemaphoreState> = entries
+            .iter()
+            .map(|(name, entry)| SemaphoreState {
+                name: name.clone(),
+                kind: entry.kind,
+                permits: entry.semaphore.permits(),
+                max_permits: entry.semaphore.max_permits(),
+                available: entry.semaphore.available_permits(),
+                held: entry.semaphore.held_permits(),
+                waiting: entry.semaphore.waiting_threads(),
+                draining: entry.semaphore.is_draining(),
+            })
+            .collect();
+        states.sort_by(|a, b| a.name.cmp(&b.name));
+        states
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for state in self.dump() {
+            writeln!(f, "{}", state)?;
+        }
+        Ok(())
+    }
+}
+
+// 확장자가 .json이면 JSON, 아니면 TOML로 읽는다.
+fn read_config(path: &Path) -> Result<Vec<SemaphoreConfig>, ConfigError> {
+    let input = fs::read_to_string(path).map_err(|error| ConfigError::Io(format!("{}: {}", path.display(), error)))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => config::parse_json(&input),
+        _ => config::parse_toml(&input),
+    }
+}
+
+pub fn global() -> &'static Registry {
+    static GLOBAL: OnceLock<Registry> = OnceLock::new();
+    GLOBAL.get_or_init(Registry::new)
+}
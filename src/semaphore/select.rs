@@ -0,0 +1,100 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use super::counting_semaphore::CountingSemaphore;
+use super::error::AcquireError;
+
+// 감시 중인 세마포어 중 하나라도 release되면 generation이 올라간다.
+// try_acquire를 돌기 전에 본 generation과 비교하므로 그 사이의 release를 놓치지 않는다.
+pub(crate) struct SelectWaker {
+    generation: Mutex<u64>,
+    cond: Condvar,
+}
+
+impl SelectWaker {
+    fn new() -> Self {
+        Self { generation: Mutex::new(0), cond: Condvar::new() }
+    }
+
+    pub(crate) fn wake(&self) {
+        *self.generation.lock().unwrap() += 1;
+        self.cond.notify_all();
+    }
+
+    fn generation(&self) -> u64 {
+        *self.generation.lock().unwrap()
+    }
+
+    // generation이 seen에서 바뀔 때까지 기다린다. deadline을 넘기면 false.
+    fn wait_changed(&self, seen: u64, deadline: Option<Instant>) -> bool {
+        let mut generation = self.generation.lock().unwrap();
+        while *generation == seen {
+            generation = match deadline {
+                None => self.cond.wait(generation).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return false;
+                    }
+                    self.cond.wait_timeout(generation, deadline - now).unwrap().0
+                }
+            };
+        }
+        true
+    }
+}
+
+struct Registration<'a> {
+    semaphores: &'a [&'a CountingSemaphore],
+    waker: Arc<SelectWaker>,
+}
+
+impl<'a> Registration<'a> {
+    fn new(semaphores: &'a [&'a CountingSemaphore]) -> Self {
+        let waker = Arc::new(SelectWaker::new());
+        for semaphore in semaphores {
+            semaphore.add_select_waker(&waker);
+        }
+        Self { semaphores, waker }
+    }
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        for semaphore in self.semaphores {
+            semaphore.remove_select_waker(&self.waker);
+        }
+    }
+}
+
+pub fn try_select_acquire(semaphores: &[&CountingSemaphore]) -> Result<usize, AcquireError> {
+    semaphores
+        .iter()
+        .position(|semaphore| semaphore.try_acquire().is_ok())
+        .ok_or(AcquireError::WouldBlock)
+}
+
+pub fn select_acquire(semaphores: &[&CountingSemaphore]) -> usize {
+    select_inner(semaphores, None).unwrap()
+}
+
+pub fn select_acquire_timeout(semaphores: &[&CountingSemaphore], timeout: Duration) -> Result<usize, AcquireError> {
+    select_inner(semaphores, Some(Instant::now() + timeout))
+}
+
+fn select_inner(semaphores: &[&CountingSemaphore], deadline: Option<Instant>) -> Result<usize, AcquireError> {
+    assert!(!semaphores.is_empty(), "select_acquire needs at least one semaphore");
+    if let Ok(index) = try_select_acquire(semaphores) {
+        return Ok(index);
+    }
+
+    let registration = Registration::new(semaphores);
+    loop {
+        let seen = registration.waker.generation();
+        if let Ok(index) = try_select_acquire(semaphores) {
+            return Ok(index);
+        }
+        if !registration.waker.wait_changed(seen, deadline) {
+            return Err(AcquireError::Timeout);
+        }
+    }
+}
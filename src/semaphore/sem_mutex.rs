@@ -0,0 +1,127 @@
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+use super::binary_semaphore::BinarySemaphore;
+use super::error::AcquireError;
+
+// BinarySemaphore가 보호하는 데이터를 함께 소유한다.
+pub struct SemMutex<T> {
+    semaphore: BinarySemaphore,
+    data: UnsafeCell<T>,
+}
+
+// 세마포어를 잡은 스레드만 data에 접근하므로 Mutex<T>와 같은 조건을 요구한다.
+unsafe impl<T: Send> Send for SemMutex<T> {}
+unsafe impl<T: Send> Sync for SemMutex<T> {}
+
+impl<T> SemMutex<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            semaphore: BinarySemaphore::untracked(),
+            data: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn lock(&self) -> SemMutexGuard<'_, T> {
+        self.semaphore.acquire();
+        SemMutexGuard { mutex: self, _not_send: PhantomData }
+    }
+
+    pub fn try_lock(&self) -> Result<SemMutexGuard<'_, T>, AcquireError> {
+        self.semaphore.try_acquire()?;
+        Ok(SemMutexGuard { mutex: self, _not_send: PhantomData })
+    }
+
+    pub fn lock_timeout(&self, timeout: Duration) -> Result<SemMutexGuard<'_, T>, AcquireError> {
+        self.semaphore.acquire_timeout(timeout)?;
+        Ok(SemMutexGuard { mutex: self, _not_send: PhantomData })
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: Default> Default for SemMutex<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+// 일반 guard는 MutexGuard처럼 잡은 스레드에서만 쓰도록 Send가 아니다.
+pub struct SemMutexGuard<'a, T> {
+    mutex: &'a SemMutex<T>,
+    _not_send: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for SemMutexGuard<'_, T> {}
+
+impl<'a, T> SemMutexGuard<'a, T> {
+    // 이진 세마포어는 잡은 스레드가 아니어도 풀 수 있으므로, 잠금을 다른 스레드로 넘길 수 있는 guard로 바꾼다.
+    pub fn into_handoff(self) -> HandoffGuard<'a, T> {
+        let mutex = self.mutex;
+        std::mem::forget(self);
+        HandoffGuard { mutex }
+    }
+}
+
+impl<T> Deref for SemMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T> DerefMut for SemMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T> Drop for SemMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.semaphore.release();
+    }
+}
+
+// 잠금을 쥔 채로 다른 스레드에 보낼 수 있는 guard. 받은 스레드에서 drop하면 그 스레드가 잠금을 푼다.
+pub struct HandoffGuard<'a, T> {
+    mutex: &'a SemMutex<T>,
+}
+
+unsafe impl<T: Send> Send for HandoffGuard<'_, T> {}
+unsafe impl<T: Sync> Sync for HandoffGuard<'_, T> {}
+
+impl<'a, T> HandoffGuard<'a, T> {
+    pub fn into_guard(self) -> SemMutexGuard<'a, T> {
+        let mutex = self.mutex;
+        std::mem::forget(self);
+        SemMutexGuard { mutex, _not_send: PhantomData }
+    }
+}
+
+impl<T> Deref for HandoffGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T> DerefMut for HandoffGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T> Drop for HandoffGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.semaphore.release();
+    }
+}
@@ -1,35 +1,394 @@
 use std::collections::VecDeque;
+use std::fmt;
 use std::sync::{Condvar, Mutex, Arc};
+use std::thread::{self, Thread, ThreadId};
+use std::time::{Duration, Instant};
+use super::drain::{self, DrainPolicy, DrainState};
+use super::error::AcquireError;
+use super::ownership::OwnershipTracker;
+use super::pause::PauseState;
+use super::watchdog::{HoldSlot, Watchdog};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueuePolicy {
+    Fifo,
+    // 대기열 체류 시간(sojourn)이 interval 동안 계속 target을 넘으면 head의 대기자를 실패시킨다.
+    CoDel { target: Duration, interval: Duration },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WaiterStatus {
+    Waiting,
+    Granted,
+    Shed,
+    Drained,
+}
+
+struct Waiter {
+    cvar: Condvar,
+    status: Mutex<WaiterStatus>,
+    enqueued: Instant,
+    sheddable: bool,
+    thread: Thread,
+    label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaiterInfo {
+    pub thread_id: ThreadId,
+    pub thread_name: Option<String>,
+    pub label: Option<String>,
+    pub waited: Duration,
+}
+
+impl Waiter {
+    fn info(&self, now: Instant) -> WaiterInfo {
+        WaiterInfo {
+            thread_id: self.thread.id(),
+            thread_name: self.thread.name().map(str::to_owned),
+            label: self.label.clone(),
+            waited: now.duration_since(self.enqueued),
+        }
+    }
+}
+
+#[derive(Default)]
+struct CoDelState {
+    first_above: Option<Instant>,
+    dropping: bool,
+    drop_next: Option<Instant>,
+    drop_count: u32,
+}
+
+struct State {
+    count: usize,
+    queue: VecDeque<Arc<Waiter>>,
+    codel: CoDelState,
+    shed: usize,
+    drain: DrainState,
+    pause: PauseState,
+}
+
+impl State {
+    fn gated(&self) -> bool {
+        self.drain.draining || self.pause.paused
+    }
+}
+
+// release 시 permit을 count에 돌려놓지 않고 queue의 head에 직접 넘겨주므로,
+// 새로 들어온 스레드가 먼저 기다리던 스레드를 앞지를 수 없다.
 pub struct StrongSemaphore {
-    count: Mutex<usize>,
-    queue: Mutex<VecDeque<Arc<Condvar>>>,
+    state: Mutex<State>,
+    drained: Condvar,
+    policy: QueuePolicy,
+    tracker: OwnershipTracker,
+    holds: HoldSlot,
 }
 
 impl StrongSemaphore {
     pub fn new(count: usize) -> Self {
+        Self::with_policy(count, QueuePolicy::Fifo)
+    }
+
+    pub fn with_policy(count: usize, policy: QueuePolicy) -> Self {
         Self {
-            count: Mutex::new(count),
-            queue: Mutex::new(VecDeque::new()),
+            state: Mutex::new(State {
+                count,
+                queue: VecDeque::new(),
+                codel: CoDelState::default(),
+                shed: 0,
+                drain: DrainState::default(),
+                pause: PauseState::default(),
+            }),
+            drained: Condvar::new(),
+            policy,
+            tracker: OwnershipTracker::new("StrongSemaphore"),
+            holds: HoldSlot::default(),
         }
     }
 
+    pub fn with_drain_policy(mut self, policy: DrainPolicy) -> Self {
+        self.state.get_mut().unwrap().drain.policy = policy;
+        self
+    }
+
+    pub fn add_permits(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.count += n;
+        self.dispatch(&mut state);
+    }
+
+    pub fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.holds.watch(watchdog, name)
+    }
+
+    pub fn shed_count(&self) -> usize {
+        self.state.lock().unwrap().shed
+    }
+
+    // acquire로 들어온 대기자는 실패를 돌려줄 수 없으므로 shedding과 drain 거절 대상에서 제외된다.
     pub fn acquire(&self) {
-        let mut count = self.count.lock().unwrap();
-        if *count == 0 {
-            let cvar = Arc::new(Condvar::new());
-            self.queue.lock().unwrap().push_back(cvar.clone());
-            while *count == 0 {
-                count = cvar.wait(count).unwrap();
-            }
+        let _ = self.acquire_inner(false, None, None);
+    }
+
+    // label은 queue_snapshot에 그대로 보여 어떤 작업이 기다리는지 구분하는 데 쓴다.
+    pub fn acquire_labeled(&self, label: &str) {
+        let _ = self.acquire_inner(false, None, Some(label));
+    }
+
+    pub fn acquire_or_shed(&self) -> Result<(), AcquireError> {
+        self.acquire_inner(true, None, None)
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if state.drain.rejects() {
+            return Err(AcquireError::Draining);
+        }
+        if state.gated() || state.count == 0 || !state.queue.is_empty() {
+            return Err(AcquireError::WouldBlock);
         }
+        state.count -= 1;
+        state.drain.on_grant(1);
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+        Ok(())
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.acquire_inner(true, Some(Instant::now() + timeout), None)
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().count
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        self.state.lock().unwrap().queue.len()
+    }
+
+    pub fn forget_permits(&self, n: usize) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let forgotten = n.min(state.count);
+        state.count -= forgotten;
+        forgotten
+    }
+
+    pub fn forget_held_permit(&self) {
+        self.holds.on_release(1);
+        if self.state.lock().unwrap().drain.on_return(1) {
+            self.drained.notify_all();
+        }
+        self.tracker.on_release(1);
+    }
+
+    // 대기열 앞에서부터, 즉 다음에 permit을 받을 순서대로 돌려준다.
+    pub fn queue_snapshot(&self) -> Vec<WaiterInfo> {
+        let state = self.state.lock().unwrap();
+        let now = Instant::now();
+        state.queue.iter().map(|waiter| waiter.info(now)).collect()
     }
 
     pub fn release(&self) {
-        let mut count = self.count.lock().unwrap();
-        *count += 1;
-        if let Some(cvar) = self.queue.lock().unwrap().pop_front() {
-            cvar.notify_one();
+        self.holds.on_release(1);
+        let mut state = self.state.lock().unwrap();
+        state.count += 1;
+        if state.drain.on_return(1) {
+            self.drained.notify_all();
         }
+        self.dispatch(&mut state);
+        drop(state);
+        self.tracker.on_release(1);
     }
-}
\ No newline at end of file
+
+    // drain 중에는 release된 permit도 대기자에게 넘기지 않으므로 대기자는 순서를 유지한 채 reopen을 기다린다.
+    pub fn drain(&self) {
+        let _ = self.drain_inner(None);
+    }
+
+    pub fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.drain_inner(Some(timeout))
+    }
+
+    pub fn reopen(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = false;
+        self.dispatch(&mut state);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.state.lock().unwrap().drain.draining
+    }
+
+    // pause 중에도 release된 permit은 count에 쌓이고, 대기열은 그대로 두었다가 resume 때 순서대로 넘겨준다.
+    pub fn pause(&self) {
+        self.state.lock().unwrap().pause.paused = true;
+    }
+
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.pause.paused = false;
+        self.dispatch(&mut state);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().pause.paused
+    }
+
+    pub fn blocked_by_pause(&self) -> usize {
+        self.state.lock().unwrap().pause.blocked
+    }
+
+    fn drain_inner(&self, timeout: Option<Duration>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = true;
+        if state.drain.policy == DrainPolicy::Reject {
+            let (rejected, kept): (VecDeque<_>, VecDeque<_>) =
+                state.queue.drain(..).partition(|w| w.sheddable);
+            state.queue = kept;
+            for waiter in rejected {
+                *waiter.status.lock().unwrap() = WaiterStatus::Drained;
+                waiter.cvar.notify_one();
+            }
+        }
+        drain::wait_drained(state, &self.drained, |s| &s.drain, timeout)
+    }
+
+    fn acquire_inner(&self, sheddable: bool, deadline: Option<Instant>, label: Option<&str>) -> Result<(), AcquireError> {
+        let result = self.wait_for_permit(sheddable, deadline, label);
+        if result.is_ok() {
+            self.tracker.on_acquire(1);
+            self.holds.on_acquire(1);
+        }
+        result
+    }
+
+    fn wait_for_permit(&self, sheddable: bool, deadline: Option<Instant>, label: Option<&str>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if sheddable && state.drain.rejects() {
+            return Err(AcquireError::Draining);
+        }
+        if !state.gated() && state.count > 0 && state.queue.is_empty() {
+            state.count -= 1;
+            state.drain.on_grant(1);
+            return Ok(());
+        }
+
+        let waiter = Arc::new(Waiter {
+            cvar: Condvar::new(),
+            status: Mutex::new(WaiterStatus::Waiting),
+            enqueued: Instant::now(),
+            sheddable,
+            thread: thread::current(),
+            label: label.map(str::to_owned),
+        });
+        state.queue.push_back(Arc::clone(&waiter));
+        state.pause.on_arrival();
+
+        loop {
+            match *waiter.status.lock().unwrap() {
+                WaiterStatus::Granted => return Ok(()),
+                WaiterStatus::Shed => return Err(AcquireError::Shed),
+                WaiterStatus::Drained => return Err(AcquireError::Draining),
+                WaiterStatus::Waiting => {}
+            }
+            state = match deadline {
+                None => waiter.cvar.wait(state).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        state.queue.retain(|w| !Arc::ptr_eq(w, &waiter));
+                        return Err(AcquireError::Timeout);
+                    }
+                    waiter.cvar.wait_timeout(state, deadline - now).unwrap().0
+                }
+            };
+        }
+    }
+
+    fn dispatch(&self, state: &mut State) {
+        if state.gated() {
+            return;
+        }
+        while state.count > 0 {
+            let waiter = match state.queue.pop_front() {
+                Some(waiter) => waiter,
+                None => return,
+            };
+
+            let status = if waiter.sheddable && self.should_shed(state, &waiter) {
+                state.shed += 1;
+                WaiterStatus::Shed
+            } else {
+                state.count -= 1;
+                state.drain.on_grant(1);
+                WaiterStatus::Granted
+            };
+            *waiter.status.lock().unwrap() = status;
+            waiter.cvar.notify_one();
+        }
+    }
+
+    // CoDel의 dequeue 단계를 permit 전달 시점에 적용한다.
+    fn should_shed(&self, state: &mut State, waiter: &Waiter) -> bool {
+        let (target, interval) = match self.policy {
+            QueuePolicy::Fifo => return false,
+            QueuePolicy::CoDel { target, interval } => (target, interval),
+        };
+        let now = Instant::now();
+        let codel = &mut state.codel;
+
+        let ok_to_drop = if now.duration_since(waiter.enqueued) < target {
+            codel.first_above = None;
+            false
+        } else {
+            match codel.first_above {
+                None => {
+                    codel.first_above = Some(now + interval);
+                    false
+                }
+                Some(first_above) => now >= first_above,
+            }
+        };
+
+        let control_law = |from: Instant, count: u32| from + interval.div_f64((count as f64).sqrt());
+
+        if codel.dropping {
+            if !ok_to_drop {
+                codel.dropping = false;
+                return false;
+            }
+            match codel.drop_next {
+                Some(drop_next) if now >= drop_next => {
+                    codel.drop_count += 1;
+                    codel.drop_next = Some(control_law(drop_next, codel.drop_count));
+                    true
+                }
+                _ => false,
+            }
+        } else if ok_to_drop {
+            codel.dropping = true;
+            codel.drop_count = 1;
+            codel.drop_next = Some(control_law(now, 1));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl fmt::Debug for StrongSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let queue: Vec<WaiterInfo> = state.queue.iter().map(|waiter| waiter.info(now)).collect();
+        f.debug_struct("StrongSemaphore")
+            .field("available_permits", &state.count)
+            .field("waiting_threads", &state.queue.len())
+            .field("policy", &self.policy)
+            .field("draining", &state.drain.draining)
+            .field("paused", &state.pause.paused)
+            .field("queue", &queue)
+            .finish()
+    }
+}
@@ -0,0 +1,177 @@
+use std::backtrace::Backtrace;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::thread::{self, JoinHandle, ThreadId};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct HoldReport {
+    pub semaphore: String,
+    pub thread_id: ThreadId,
+    pub thread_name: Option<String>,
+    pub held_for: Duration,
+    // threshold를 몇 배 넘겼는지. 같은 permit에 대해 1, 2, 3... 순서로 다시 보고된다.
+    pub escalation: u32,
+    pub backtrace: Arc<Backtrace>,
+}
+
+struct Hold {
+    thread_id: ThreadId,
+    thread_name: Option<String>,
+    since: Instant,
+    backtrace: Arc<Backtrace>,
+    reported: u32,
+}
+
+struct Monitor {
+    name: String,
+    holds: Mutex<Vec<Hold>>,
+}
+
+type Callback = Box<dyn Fn(&HoldReport) + Send + Sync>;
+
+struct WatchdogInner {
+    threshold: Duration,
+    monitors: Mutex<Vec<Weak<Monitor>>>,
+    on_report: Callback,
+    stop: AtomicBool,
+}
+
+impl WatchdogInner {
+    fn scan(&self) {
+        let now = Instant::now();
+        let mut reports = Vec::new();
+        self.monitors.lock().unwrap().retain(|weak| {
+            let monitor = match weak.upgrade() {
+                Some(monitor) => monitor,
+                None => return false,
+            };
+            for hold in monitor.holds.lock().unwrap().iter_mut() {
+                let held_for = now.duration_since(hold.since);
+                let level = (held_for.as_nanos() / self.threshold.as_nanos()) as u32;
+                if level > hold.reported {
+                    hold.reported = level;
+                    reports.push(HoldReport {
+                        semaphore: monitor.name.clone(),
+                        thread_id: hold.thread_id,
+                        thread_name: hold.thread_name.clone(),
+                        held_for,
+                        escalation: level,
+                        backtrace: Arc::clone(&hold.backtrace),
+                    });
+                }
+            }
+            true
+        });
+        for report in &reports {
+            (self.on_report)(report);
+        }
+    }
+}
+
+// permit을 threshold보다 오래 들고 있는 스레드를 주기적으로 찾아 보고한다.
+// 감시 대상 세마포어는 acquire할 때마다 backtrace를 잡으므로 필요한 세마포어에만 붙인다.
+pub struct Watchdog {
+    inner: Arc<WatchdogInner>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    pub fn new<F>(threshold: Duration, check_interval: Duration, on_report: F) -> Self
+    where
+        F: Fn(&HoldReport) + Send + Sync + 'static,
+    {
+        assert!(!threshold.is_zero(), "threshold must be non-zero");
+        let inner = Arc::new(WatchdogInner {
+            threshold,
+            monitors: Mutex::new(Vec::new()),
+            on_report: Box::new(on_report),
+            stop: AtomicBool::new(false),
+        });
+        let handle = {
+            let inner = Arc::clone(&inner);
+            thread::Builder::new()
+                .name("semaphore-watchdog".into())
+                .spawn(move || {
+                    while !inner.stop.load(Ordering::SeqCst) {
+                        thread::park_timeout(check_interval);
+                        inner.scan();
+                    }
+                })
+                .unwrap()
+        };
+        Self { inner, handle: Some(handle) }
+    }
+
+    pub fn threshold(&self) -> Duration {
+        self.inner.threshold
+    }
+
+    fn register(&self, name: &str) -> Arc<Monitor> {
+        let monitor = Arc::new(Monitor { name: name.to_owned(), holds: Mutex::new(Vec::new()) });
+        self.inner.monitors.lock().unwrap().push(Arc::downgrade(&monitor));
+        monitor
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.inner.stop.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+// 각 세마포어 타입이 들고 있는 감시 지점. watch가 호출되기 전에는 아무 비용도 들지 않는다.
+#[derive(Default)]
+pub(crate) struct HoldSlot {
+    monitor: OnceLock<Arc<Monitor>>,
+}
+
+impl HoldSlot {
+    pub(crate) fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.monitor.set(watchdog.register(name)).is_ok()
+    }
+
+    pub(crate) fn on_acquire(&self, permits: usize) {
+        let monitor = match self.monitor.get() {
+            Some(monitor) => monitor,
+            None => return,
+        };
+        let current = thread::current();
+        let backtrace = Arc::new(Backtrace::force_capture());
+        let mut holds = monitor.holds.lock().unwrap();
+        for _ in 0..permits {
+            holds.push(Hold {
+                thread_id: current.id(),
+                thread_name: current.name().map(str::to_owned),
+                since: Instant::now(),
+                backtrace: Arc::clone(&backtrace),
+                reported: 0,
+            });
+        }
+    }
+
+    // 반납한 스레드가 가장 최근에 잡은 permit을 지운다. 다른 스레드가 대신 반납했다면 가장 오래된 것을 지운다.
+    pub(crate) fn on_release(&self, permits: usize) {
+        let monitor = match self.monitor.get() {
+            Some(monitor) => monitor,
+            None => return,
+        };
+        let id = thread::current().id();
+        let mut holds = monitor.holds.lock().unwrap();
+        for _ in 0..permits {
+            match holds.iter().rposition(|hold| hold.thread_id == id) {
+                Some(index) => {
+                    holds.remove(index);
+                }
+                None if !holds.is_empty() => {
+                    holds.remove(0);
+                }
+                None => return,
+            }
+        }
+    }
+}
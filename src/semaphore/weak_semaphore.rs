@@ -1,29 +1,355 @@
-use std::sync::{Condvar, Mutex};
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use super::drain::{self, DrainPolicy, DrainState};
+use super::error::AcquireError;
+use super::ownership::OwnershipTracker;
+use super::pause::PauseState;
+use super::watchdog::{HoldSlot, Watchdog};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WakePolicy {
+    // release마다 가장 오래 잠든 대기자 하나만 깨운다. 깨어난 뒤에는 새로 온 스레드와 경쟁한다.
+    #[default]
+    NotifyOne,
+    NotifyAll,
+    // 일부러 불공정하게 만들어 순서에 기대는 코드를 찾는 테스트용.
+    Random,
+}
+
+// 대기자마다 Condvar를 두어 깨울 대상을 직접 고른다. signalled는 state lock 아래에서만 바뀐다.
+// sleepers는 seq 순(먼저 잠든 순)으로 유지한다.
+struct Sleeper {
+    cvar: Condvar,
+    signalled: AtomicBool,
+    seq: u64,
+}
+
+struct State {
+    permits: usize,
+    waiting: usize,
+    drain: DrainState,
+    pause: PauseState,
+    sleepers: Vec<Arc<Sleeper>>,
+    next_seq: u64,
+    rng: u64,
+    // 아무도 깨우지 않았는데 wait에서 돌아온 횟수.
+    spurious_wakeups: usize,
+    // 깨웠지만 permit을 얻지 못하고 다시 잠든 횟수.
+    futile_wakeups: usize,
+}
+
+impl State {
+    fn admits(&self) -> bool {
+        !self.drain.draining && !self.pause.paused && self.permits > 0
+    }
+}
 
 pub struct WeakSemaphore {
-    count: Mutex<usize>,
-    cond: Condvar,
+    state: Mutex<State>,
+    wake_policy: WakePolicy,
+    drained: Condvar,
+    tracker: OwnershipTracker,
+    holds: HoldSlot,
 }
 
 impl WeakSemaphore {
     pub fn new(count: usize) -> Self {
         Self {
-            count: Mutex::new(count),
-            cond: Condvar::new(),
+            state: Mutex::new(State {
+                permits: count,
+                waiting: 0,
+                drain: DrainState::default(),
+                pause: PauseState::default(),
+                sleepers: Vec::new(),
+                next_seq: 0,
+                rng: RandomState::new().build_hasher().finish() | 1,
+                spurious_wakeups: 0,
+                futile_wakeups: 0,
+            }),
+            wake_policy: WakePolicy::default(),
+            drained: Condvar::new(),
+            tracker: OwnershipTracker::new("WeakSemaphore"),
+            holds: HoldSlot::default(),
         }
     }
 
+    pub fn with_drain_policy(mut self, policy: DrainPolicy) -> Self {
+        self.state.get_mut().unwrap().drain.policy = policy;
+        self
+    }
+
+    pub fn with_wake_policy(mut self, policy: WakePolicy) -> Self {
+        self.wake_policy = policy;
+        self
+    }
+
     pub fn acquire(&self) {
-        let mut count = self.count.lock().unwrap();
-        while *count == 0 {
-            count = self.cond.wait(count).unwrap();
+        let mut state = self.state.lock().unwrap();
+        state.pause.on_arrival();
+        if !state.admits() {
+            state.waiting += 1;
+            let sleeper = Self::enqueue(&mut state);
+            while !state.admits() {
+                state = self.sleep(state, &sleeper, None);
+            }
+            Self::dequeue(&mut state, &sleeper);
+            state.waiting -= 1;
+        }
+        state.permits -= 1;
+        state.drain.on_grant(1);
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+    }
+
+    pub fn try_acquire(&self) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        if state.drain.rejects() {
+            return Err(AcquireError::Draining);
+        }
+        if !state.admits() {
+            return Err(AcquireError::WouldBlock);
+        }
+        state.permits -= 1;
+        state.drain.on_grant(1);
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+        Ok(())
+    }
+
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.state.lock().unwrap();
+        state.pause.on_arrival();
+        if !state.admits() {
+            state.waiting += 1;
+            let sleeper = Self::enqueue(&mut state);
+            while !state.admits() {
+                let now = Instant::now();
+                let error = if state.drain.rejects() {
+                    AcquireError::Draining
+                } else if now >= deadline {
+                    AcquireError::Timeout
+                } else {
+                    state = self.sleep(state, &sleeper, Some(deadline));
+                    continue;
+                };
+                Self::dequeue(&mut state, &sleeper);
+                state.waiting -= 1;
+                // 깨워진 직후 타임아웃되었다면 그 wakeup을 다른 대기자에게 넘긴다.
+                if state.admits() {
+                    self.wake(&mut state);
+                }
+                return Err(error);
+            }
+            Self::dequeue(&mut state, &sleeper);
+            state.waiting -= 1;
         }
-        *count -= 1;
+        state.permits -= 1;
+        state.drain.on_grant(1);
+        self.tracker.on_acquire(1);
+        self.holds.on_acquire(1);
+        Ok(())
+    }
+
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    pub fn waiting_threads(&self) -> usize {
+        self.state.lock().unwrap().waiting
+    }
+
+    pub fn spurious_wakeups(&self) -> usize {
+        self.state.lock().unwrap().spurious_wakeups
+    }
+
+    pub fn futile_wakeups(&self) -> usize {
+        self.state.lock().unwrap().futile_wakeups
+    }
+
+    pub fn forget_permits(&self, n: usize) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let forgotten = n.min(state.permits);
+        state.permits -= forgotten;
+        forgotten
+    }
+
+    pub fn forget_held_permit(&self) {
+        self.holds.on_release(1);
+        if self.state.lock().unwrap().drain.on_return(1) {
+            self.drained.notify_all();
+        }
+        self.tracker.on_release(1);
+    }
+
+    pub fn add_permits(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.permits += n;
+        for _ in 0..n {
+            if !state.admits() {
+                break;
+            }
+            self.wake(&mut state);
+        }
+    }
+
+    pub fn watch(&self, watchdog: &Watchdog, name: &str) -> bool {
+        self.holds.watch(watchdog, name)
+    }
+
+    pub fn drain(&self) {
+        let _ = self.drain_inner(None);
+    }
+
+    pub fn drain_timeout(&self, timeout: Duration) -> Result<(), AcquireError> {
+        self.drain_inner(Some(timeout))
+    }
+
+    pub fn reopen(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = false;
+        Self::wake_all(&mut state);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.state.lock().unwrap().drain.draining
+    }
+
+    pub fn pause(&self) {
+        self.state.lock().unwrap().pause.paused = true;
+    }
+
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.pause.paused = false;
+        Self::wake_all(&mut state);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().pause.paused
+    }
+
+    pub fn blocked_by_pause(&self) -> usize {
+        self.state.lock().unwrap().pause.blocked
+    }
+
+    fn drain_inner(&self, timeout: Option<Duration>) -> Result<(), AcquireError> {
+        let mut state = self.state.lock().unwrap();
+        state.drain.draining = true;
+        if state.drain.policy == DrainPolicy::Reject {
+            Self::wake_all(&mut state);
+        }
+        drain::wait_drained(state, &self.drained, |s| &s.drain, timeout)
     }
 
     pub fn release(&self) {
-        let mut count = self.count.lock().unwrap();
-        *count += 1;
-        self.cond.notify_all();
+        self.holds.on_release(1);
+        let mut state = self.state.lock().unwrap();
+        state.permits += 1;
+        if state.drain.on_return(1) {
+            self.drained.notify_all();
+        }
+        // pause나 drain 중이면 깨워도 헛수고이므로 resume/reopen 때 한꺼번에 깨운다.
+        if state.admits() {
+            self.wake(&mut state);
+        }
+        drop(state);
+        self.tracker.on_release(1);
     }
-}
\ No newline at end of file
+
+    fn enqueue(state: &mut State) -> Arc<Sleeper> {
+        let sleeper = Arc::new(Sleeper { cvar: Condvar::new(), signalled: AtomicBool::new(false), seq: state.next_seq });
+        state.next_seq += 1;
+        state.sleepers.push(Arc::clone(&sleeper));
+        sleeper
+    }
+
+    // 깨워지지 않은 채 떠나는 대기자(타임아웃, drain 거절)를 목록에서 뺀다.
+    fn dequeue(state: &mut State, sleeper: &Arc<Sleeper>) {
+        state.sleepers.retain(|s| !Arc::ptr_eq(s, sleeper));
+    }
+
+    // 깨워졌는데 그 사이 다른 스레드가 permit을 가져갔으면 futile로 센다.
+    // 다시 잠들 때는 원래 순서 자리로 돌아가므로 가장 오래 기다린 대기자가 계속 먼저 깨워진다.
+    fn sleep<'a>(
+        &self,
+        mut state: MutexGuard<'a, State>,
+        sleeper: &Arc<Sleeper>,
+        deadline: Option<Instant>,
+    ) -> MutexGuard<'a, State> {
+        let mut timed_out = false;
+        state = match deadline {
+            None => sleeper.cvar.wait(state).unwrap(),
+            Some(deadline) => {
+                let (state, result) = sleeper
+                    .cvar
+                    .wait_timeout(state, deadline.saturating_duration_since(Instant::now()))
+                    .unwrap();
+                timed_out = result.timed_out();
+                state
+            }
+        };
+
+        if sleeper.signalled.swap(false, Ordering::Relaxed) {
+            if state.permits == 0 {
+                state.futile_wakeups += 1;
+            }
+            if !state.admits() {
+                let index = state.sleepers.partition_point(|s| s.seq < sleeper.seq);
+                state.sleepers.insert(index, Arc::clone(sleeper));
+            }
+        } else if !timed_out {
+            state.spurious_wakeups += 1;
+        }
+        state
+    }
+
+    fn wake(&self, state: &mut State) {
+        if state.sleepers.is_empty() {
+            return;
+        }
+        let sleeper = match self.wake_policy {
+            WakePolicy::NotifyAll => return Self::wake_all(state),
+            WakePolicy::NotifyOne => state.sleepers.remove(0),
+            WakePolicy::Random => {
+                // xorshift64
+                state.rng ^= state.rng << 13;
+                state.rng ^= state.rng >> 7;
+                state.rng ^= state.rng << 17;
+                let index = (state.rng % state.sleepers.len() as u64) as usize;
+                state.sleepers.remove(index)
+            }
+        };
+        Self::signal(&sleeper);
+    }
+
+    fn wake_all(state: &mut State) {
+        for sleeper in state.sleepers.drain(..) {
+            Self::signal(&sleeper);
+        }
+    }
+
+    fn signal(sleeper: &Sleeper) {
+        sleeper.signalled.store(true, Ordering::Relaxed);
+        sleeper.cvar.notify_one();
+    }
+}
+
+impl fmt::Debug for WeakSemaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.lock().unwrap();
+        f.debug_struct("WeakSemaphore")
+            .field("available_permits", &state.permits)
+            .field("waiting_threads", &state.waiting)
+            .field("draining", &state.drain.draining)
+            .field("paused", &state.pause.paused)
+            .field("wake_policy", &self.wake_policy)
+            .field("spurious_wakeups", &state.spurious_wakeups)
+            .field("futile_wakeups", &state.futile_wakeups)
+            .finish()
+    }
+}
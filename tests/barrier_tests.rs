@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod barrier_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::barrier::Barrier;
+    use concurrency_project::semaphore::error::AcquireError;
+
+    // 모든 스레드가 도착하기 전에는 아무도 barrier를 통과하지 못해야 하며, 리더는 정확히 하나여야 합니다.
+    #[test]
+    fn test_barrier_releases_all_parties() {
+        let num_threads = 5;
+        let barrier = Arc::new(Barrier::new(num_threads));
+        let arrived = Arc::new(Mutex::new(0));
+        let mut handles = vec![];
+
+        for _ in 0..num_threads {
+            let barrier = Arc::clone(&barrier);
+            let arrived = Arc::clone(&arrived);
+            handles.push(thread::spawn(move || {
+                *arrived.lock().unwrap() += 1;
+                let result = barrier.wait();
+                assert_eq!(*arrived.lock().unwrap(), num_threads, "Passed the barrier too early");
+                result
+            }));
+        }
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results.iter().filter(|r| r.is_leader).count(), 1, "Exactly one leader expected");
+        assert!(results.iter().all(|r| r.generation == 0));
+        assert_eq!(barrier.generation(), 1);
+    }
+
+    // 같은 barrier를 여러 세대에 걸쳐 재사용할 수 있는지 확인합니다.
+    #[test]
+    fn test_barrier_reusable_generations() {
+        let num_threads = 3;
+        let rounds = 20;
+        let barrier = Arc::new(Barrier::new(num_threads));
+        let mut handles = vec![];
+
+        for _ in 0..num_threads {
+            let barrier = Arc::clone(&barrier);
+            handles.push(thread::spawn(move || {
+                (0..rounds).map(|_| barrier.wait().generation).collect::<Vec<_>>()
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), (0..rounds).collect::<Vec<_>>());
+        }
+    }
+
+    // 타임아웃된 스레드는 도착을 철회하므로 이후 세대가 정상적으로 진행되어야 합니다.
+    #[test]
+    fn test_barrier_wait_timeout() {
+        let barrier = Arc::new(Barrier::new(2));
+        assert_eq!(barrier.wait_timeout(Duration::from_millis(50)), Err(AcquireError::Timeout));
+        assert_eq!(barrier.generation(), 0);
+
+        let handle = {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || barrier.wait_timeout(Duration::from_secs(5)))
+        };
+        let result = barrier.wait();
+        assert_eq!(handle.join().unwrap().unwrap().generation, 0);
+        assert_eq!(result.generation, 0);
+        assert_eq!(barrier.generation(), 1);
+    }
+
+    // party가 0개인 barrier는 아무도 통과시킬 수 없으므로 만들 수 없어야 합니다.
+    #[test]
+    #[should_panic(expected = "at least one party")]
+    fn test_barrier_rejects_zero_parties() {
+        let _ = Barrier::new(0);
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod bounded_queue_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::bounded_queue::{
+        BoundedQueue, RecvError, RecvTimeoutError, SendError, SendTimeoutError, TryRecvError, TrySendError,
+    };
+
+    // 여러 생산자와 소비자가 동시에 동작해도 모든 값이 정확히 한 번씩 전달되고, 버퍼가 용량을 넘지 않아야 합니다.
+    #[test]
+    fn test_bounded_queue_mpmc() {
+        let (tx, rx) = BoundedQueue::channel(4);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut handles = vec![];
+
+        for p in 0..4 {
+            let tx = tx.clone();
+            handles.push(thread::spawn(move || {
+                for i in 0..100 {
+                    tx.send(p * 100 + i).unwrap();
+                }
+            }));
+        }
+        drop(tx);
+
+        let mut consumers = vec![];
+        for _ in 0..3 {
+            let rx = rx.clone();
+            let received = Arc::clone(&received);
+            consumers.push(thread::spawn(move || {
+                for value in rx.iter() {
+                    received.lock().unwrap().push(value);
+                }
+            }));
+        }
+
+        for handle in handles.into_iter().chain(consumers) {
+            handle.join().unwrap();
+        }
+
+        let mut received = received.lock().unwrap().clone();
+        received.sort();
+        assert_eq!(received, (0..400).collect::<Vec<_>>());
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    // 가득 찬 큐와 빈 큐에서 try_/timeout 변형이 블록되지 않고 알맞은 오류를 돌려주는지 확인합니다.
+    #[test]
+    fn test_bounded_queue_try_and_timeout() {
+        let queue = BoundedQueue::new(1);
+        assert_eq!(queue.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(queue.recv_timeout(Duration::from_millis(20)), Err(RecvTimeoutError::Timeout));
+
+        assert_eq!(queue.try_send(1), Ok(()));
+        assert_eq!(queue.try_send(2), Err(TrySendError::Full(2)));
+        assert_eq!(queue.send_timeout(3, Duration::from_millis(20)), Err(SendTimeoutError::Timeout(3)));
+
+        assert_eq!(queue.recv_timeout(Duration::from_millis(20)), Ok(1));
+        assert!(queue.is_empty());
+    }
+
+    // 닫힌 뒤에는 송신이 실패하고, 수신자는 남은 값을 모두 꺼낸 다음 Disconnected를 받아야 합니다.
+    #[test]
+    fn test_bounded_queue_close_drains_then_disconnects() {
+        let queue = Arc::new(BoundedQueue::new(2));
+        queue.send(1).unwrap();
+        queue.send(2).unwrap();
+
+        let blocked_sender = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.send(3))
+        };
+        thread::sleep(Duration::from_millis(50));
+
+        queue.close();
+        assert_eq!(blocked_sender.join().unwrap(), Err(SendError(3)));
+        assert_eq!(queue.try_send(4), Err(TrySendError::Disconnected(4)));
+
+        assert_eq!(queue.recv(), Ok(1));
+        assert_eq!(queue.recv(), Ok(2));
+        assert_eq!(queue.recv(), Err(RecvError));
+        assert_eq!(queue.try_recv(), Err(TryRecvError::Disconnected));
+        assert_eq!(queue.recv_timeout(Duration::from_millis(20)), Err(RecvTimeoutError::Disconnected));
+    }
+
+    // 모든 수신자가 블록된 상태에서 마지막 송신자가 사라지면 모두 깨어나야 합니다.
+    #[test]
+    fn test_bounded_queue_wakes_receivers_on_disconnect() {
+        let (tx, rx) = BoundedQueue::<u32>::channel(1);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let rx = rx.clone();
+                thread::spawn(move || rx.recv())
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(50));
+
+        drop(tx);
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Err(RecvError));
+        }
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod builder_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::builder::{
+        BuildError, Fairness, Semaphore, SemaphoreBuilder, TraceEvent, WaitStrategy,
+    };
+    use concurrency_project::semaphore::drain::DrainPolicy;
+    use concurrency_project::semaphore::error::{AcquireError, ReleaseError};
+
+    // 설정값으로 fairness를 골라 Box<dyn Semaphore>를 만들고, 이름과 metrics가 붙어야 합니다.
+    #[test]
+    fn test_builder_selects_fairness_at_runtime() {
+        for fairness in [Fairness::Weak, Fairness::Strong, Fairness::Priority] {
+            let semaphore: Arc<dyn Semaphore> = SemaphoreBuilder::new()
+                .name("db-writes")
+                .initial_permits(1)
+                .fairness(fairness)
+                .wait_strategy(WaitStrategy::Spin(100))
+                .metrics(true)
+                .build()
+                .unwrap()
+                .into();
+            assert_eq!(semaphore.name(), Some("db-writes"));
+
+            semaphore.acquire();
+            assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+            assert_eq!(semaphore.acquire_timeout(Duration::from_millis(10)), Err(AcquireError::Timeout));
+
+            let waiter = {
+                let semaphore = Arc::clone(&semaphore);
+                thread::spawn(move || {
+                    semaphore.acquire();
+                    semaphore.release();
+                })
+            };
+            thread::sleep(Duration::from_millis(20));
+            assert_eq!(semaphore.waiting_threads(), 1, "{:?}", fairness);
+            semaphore.release();
+            waiter.join().unwrap();
+
+            let metrics = semaphore.metrics().unwrap();
+            assert_eq!((metrics.acquired, metrics.released, metrics.timed_out, metrics.rejected), (2, 2, 1, 1));
+            assert!(metrics.max_wait >= Duration::from_millis(15));
+            assert_eq!(semaphore.available_permits(), 1);
+        }
+    }
+
+    // max_permits까지는 acquire 없이 release해서 permit을 늘릴 수 있고, 넘기면 에러로 알려주며 permit은 그대로여야 합니다.
+    #[test]
+    fn test_builder_max_permits() {
+        let error = SemaphoreBuilder::new().initial_permits(3).max_permits(2).build().err();
+        assert_eq!(error, Some(BuildError::InitialExceedsMax { initial: 3, max: 2 }));
+
+        let semaphore = SemaphoreBuilder::new().initial_permits(1).max_permits(2).build_strong().unwrap();
+        assert_eq!(semaphore.metrics(), None);
+        semaphore.release();
+        assert_eq!(semaphore.available_permits(), 2);
+        assert_eq!(semaphore.max_permits(), 2);
+
+        assert_eq!(semaphore.try_release(), Err(ReleaseError::ExceedsMax { max: 2 }));
+        semaphore.release();
+        assert_eq!(semaphore.available_permits(), 2);
+        assert_eq!(semaphore.permits(), 2);
+    }
+
+    // tracing은 호출자가 넘긴 sink로 이름과 이벤트를 보내야 하고, 상한을 넘는 release는 tracker로도 보고되어야 합니다.
+    #[test]
+    fn test_builder_tracing_sink() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let semaphore = {
+            let events = Arc::clone(&events);
+            SemaphoreBuilder::new()
+                .name("traced")
+                .initial_permits(1)
+                .tracing(move |name, event| events.lock().unwrap().push((name.map(str::to_owned), *event)))
+                .build_weak()
+                .unwrap()
+        };
+
+        semaphore.acquire();
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+        semaphore.release();
+        semaphore.release();
+
+        let events = events.lock().unwrap();
+        assert!(events.iter().all(|(name, _)| name.as_deref() == Some("traced")));
+        let kinds: Vec<_> = events.iter().map(|(_, event)| *event).collect();
+        assert!(matches!(kinds[0], TraceEvent::Acquired { .. }));
+        assert!(matches!(kinds[1], TraceEvent::AcquireFailed { error: AcquireError::WouldBlock, .. }));
+        assert_eq!(kinds[2..], [TraceEvent::Released, TraceEvent::ReleaseRejected { max: 1 }]);
+    }
+
+    // 나간 permit 없이 permit을 늘리는 것은 소유권 위반이 아니고, 상한을 넘긴 release만 보고되어야 합니다.
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_builder_reports_release_above_max() {
+        use concurrency_project::semaphore::ownership::{scoped_reporter, Misuse, Reporter};
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let _guard = scoped_reporter(Reporter::Collect(Arc::clone(&reports)));
+        let semaphore = SemaphoreBuilder::new().initial_permits(0).max_permits(1).build_strong().unwrap();
+        semaphore.set_limits(1, 2).unwrap();
+        semaphore.release();
+        semaphore.release();
+
+        let misuses: Vec<_> = reports.lock().unwrap().iter().map(|report| report.misuse).collect();
+        assert_eq!(misuses, vec![Misuse::ReleaseAboveMax { max: 2 }]);
+        assert_eq!(semaphore.available_permits(), 2);
+    }
+
+    // close behavior가 Reject이면 drain 중 타임아웃 acquire가 Draining으로 실패하고, priority 전용 API도 쓸 수 있어야 합니다.
+    #[test]
+    fn test_builder_close_behavior_and_priority() {
+        let semaphore = SemaphoreBuilder::new()
+            .initial_permits(1)
+            .close_behavior(DrainPolicy::Reject)
+            .metrics(true)
+            .build_priority()
+            .unwrap();
+
+        semaphore.acquire_with_priority(5);
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(5)), Err(AcquireError::Timeout));
+        assert!(semaphore.is_draining());
+        assert_eq!(semaphore.acquire_timeout(Duration::from_millis(50)), Err(AcquireError::Draining));
+
+        semaphore.release();
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(5)), Ok(()));
+        semaphore.reopen();
+        assert_eq!(semaphore.acquire_with_priority_timeout(1, Duration::from_millis(5)), Ok(()));
+        semaphore.release();
+        assert_eq!(semaphore.metrics().unwrap().rejected, 1);
+        assert!(format!("{:?}", semaphore).contains("PrioritySemaphore"));
+    }
+
+    // release와 set_limits가 동시에 돌아도, 모두 돌려준 뒤에는 마지막으로 정한 permit 수만큼 남아야 합니다.
+    #[test]
+    fn test_builder_release_races_set_limits() {
+        let semaphore = Arc::new(SemaphoreBuilder::new().initial_permits(4).max_permits(8).build_strong().unwrap());
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let semaphore = Arc::clone(&semaphore);
+                thread::spawn(move || {
+                    for _ in 0..20_000 {
+                        semaphore.acquire();
+                        semaphore.release();
+                    }
+                })
+            })
+            .collect();
+        let mut round = 0;
+        while workers.iter().any(|worker| !worker.is_finished()) {
+            semaphore.set_limits(if round % 2 == 0 { 1 } else { 4 }, 8).unwrap();
+            round += 1;
+        }
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        semaphore.set_limits(4, 8).unwrap();
+        assert_eq!(semaphore.held_permits(), 0);
+        assert_eq!(semaphore.available_permits(), 4);
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod bulkhead_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::bulkhead::Bulkhead;
+    use concurrency_project::semaphore::error::AcquireError;
+
+    // 동시 실행 수가 max_concurrent를 넘지 않아야 합니다.
+    #[test]
+    fn test_bulkhead_limits_concurrency() {
+        let bulkhead = Arc::new(Bulkhead::new(2, 10, Duration::from_secs(5)));
+        let counter = Arc::new(Mutex::new(0));
+        let mut handles = vec![];
+
+        for _ in 0..8 {
+            let bulkhead = Arc::clone(&bulkhead);
+            let counter = Arc::clone(&counter);
+            handles.push(thread::spawn(move || {
+                bulkhead.call(|| {
+                    *counter.lock().unwrap() += 1;
+                    assert!(*counter.lock().unwrap() <= 2, "More than two calls inside the bulkhead");
+                    thread::sleep(Duration::from_millis(10));
+                    *counter.lock().unwrap() -= 1;
+                })
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), Ok(()));
+        }
+        assert_eq!(bulkhead.rejected_count(), 0);
+    }
+
+    // 대기열이 가득 차면 즉시 Rejected를 돌려주고 거절 횟수를 세어야 합니다.
+    #[test]
+    fn test_bulkhead_rejects_over_queue_limit() {
+        let bulkhead = Arc::new(Bulkhead::new(1, 1, Duration::from_secs(5)));
+        let permit = bulkhead.acquire().unwrap();
+
+        let waiter = {
+            let bulkhead = Arc::clone(&bulkhead);
+            thread::spawn(move || bulkhead.call(|| 42))
+        };
+        while bulkhead.waiting() == 0 {
+            thread::yield_now();
+        }
+
+        assert_eq!(bulkhead.call(|| 0), Err(AcquireError::Rejected));
+        assert_eq!(bulkhead.call(|| 0), Err(AcquireError::Rejected));
+        assert_eq!(bulkhead.rejected_count(), 2);
+
+        drop(permit);
+        assert_eq!(waiter.join().unwrap(), Ok(42));
+        assert_eq!(bulkhead.available_permits(), 1);
+    }
+
+    // 최대 대기 시간을 넘기면 Timeout으로 실패해야 합니다.
+    #[test]
+    fn test_bulkhead_max_wait() {
+        let bulkhead = Bulkhead::new(1, 1, Duration::from_millis(20));
+        let _permit = bulkhead.acquire().unwrap();
+        assert_eq!(bulkhead.call(|| ()), Err(AcquireError::Timeout));
+        assert_eq!(bulkhead.timed_out_count(), 1);
+        assert_eq!(bulkhead.waiting(), 0);
+    }
+}
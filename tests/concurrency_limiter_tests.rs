@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod concurrency_limiter_tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use concurrency_project::semaphore::clock::ManualClock;
+    use concurrency_project::semaphore::concurrency_limiter::{
+        Aimd, ConcurrencyLimiter, Gradient, LimitAlgorithm, Outcome, Sample, Vegas,
+    };
+    use concurrency_project::semaphore::error::AcquireError;
+
+    fn success(latency_ms: u64, in_flight: usize) -> Sample {
+        Sample { latency: Duration::from_millis(latency_ms), in_flight, outcome: Outcome::Success }
+    }
+
+    // 한도만큼 permit이 나가면 더 이상 획득할 수 없고, 보고 없이 drop하면 한도는 그대로여야 합니다.
+    #[test]
+    fn test_limiter_enforces_current_limit() {
+        let limiter = ConcurrencyLimiter::new(Aimd::new(2, 1, 10));
+        let first = limiter.acquire();
+        let _second = limiter.try_acquire().unwrap();
+        assert_eq!(limiter.try_acquire().err(), Some(AcquireError::WouldBlock));
+        assert_eq!(limiter.acquire_timeout(Duration::from_millis(20)).err(), Some(AcquireError::Timeout));
+
+        drop(first);
+        assert_eq!(limiter.in_flight(), 1);
+        assert_eq!(limiter.limit(), 2);
+    }
+
+    // 가짜 시계로 만든 지연 시간 흐름에 따라 AIMD가 한도를 늘리고 줄이는지 확인합니다.
+    #[test]
+    fn test_aimd_grows_on_success_and_backs_off() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = ConcurrencyLimiter::with_clock(
+            Aimd::new(4, 1, 8).with_backoff(0.5).with_timeout(Duration::from_millis(100)),
+            Arc::clone(&clock),
+        );
+
+        for _ in 0..6 {
+            let permits: Vec<_> = (0..limiter.limit()).map(|_| limiter.acquire()).collect();
+            clock.advance(Duration::from_millis(10));
+            for permit in permits {
+                permit.success();
+            }
+        }
+        assert_eq!(limiter.limit(), 8, "Limit should grow up to max");
+
+        let permit = limiter.acquire();
+        clock.advance(Duration::from_millis(200));
+        permit.success();
+        assert_eq!(limiter.limit(), 4, "Slow responses should back off");
+
+        limiter.acquire().dropped();
+        assert_eq!(limiter.limit(), 2);
+    }
+
+    // Vegas는 지연 시간이 base_rtt에 가까우면 늘리고, 큐가 쌓이면 줄여야 합니다.
+    #[test]
+    fn test_vegas_follows_latency_trace() {
+        let mut vegas = Vegas::new(10, 1, 100);
+        let mut limit = vegas.initial_limit();
+        for _ in 0..10 {
+            limit = vegas.update(limit, &success(10, limit));
+        }
+        assert_eq!(limit, 20);
+
+        for _ in 0..5 {
+            limit = vegas.update(limit, &success(20, limit));
+        }
+        assert_eq!(limit, 15);
+
+        limit = vegas.update(limit, &Sample { outcome: Outcome::Dropped, ..success(10, limit) });
+        assert_eq!(limit, 7);
+    }
+
+    // Gradient는 지연이 일정하면 한도를 늘리고, 지연이 급증하면 줄여야 합니다.
+    #[test]
+    fn test_gradient_follows_latency_trace() {
+        let mut gradient = Gradient::new(20, 5, 200).with_smoothing(0.5);
+        let mut limit = gradient.initial_limit();
+        for _ in 0..10 {
+            limit = gradient.update(limit, &success(10, limit));
+        }
+        let steady = limit;
+        assert!(steady > 20, "Steady latency should let the limit grow, got {}", steady);
+
+        for _ in 0..10 {
+            limit = gradient.update(limit, &success(100, limit));
+        }
+        assert!(limit < steady, "Latency spike should shrink the limit, got {}", limit);
+        assert!(limit >= 5);
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod countdown_latch_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::countdown_latch::CountDownLatch;
+    use concurrency_project::semaphore::error::AcquireError;
+
+    // 카운트가 0이 될 때까지 모든 대기 스레드가 블록되어야 합니다.
+    #[test]
+    fn test_latch_blocks_until_zero() {
+        let latch = Arc::new(CountDownLatch::new(3));
+        let released = Arc::new(Mutex::new(0));
+        let mut handles = vec![];
+
+        for _ in 0..4 {
+            let latch = Arc::clone(&latch);
+            let released = Arc::clone(&released);
+            handles.push(thread::spawn(move || {
+                latch.wait();
+                *released.lock().unwrap() += 1;
+            }));
+        }
+
+        latch.count_down();
+        latch.count_down();
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(*released.lock().unwrap(), 0, "Latch should still be closed");
+
+        latch.count_down();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*released.lock().unwrap(), 4);
+        assert_eq!(latch.count(), 0);
+    }
+
+    // 열린 latch는 즉시 통과하고, 닫힌 latch는 타임아웃을 돌려줘야 합니다.
+    #[test]
+    fn test_latch_wait_timeout() {
+        let latch = CountDownLatch::new(1);
+        assert_eq!(latch.wait_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+
+        latch.count_down();
+        assert_eq!(latch.wait_timeout(Duration::from_millis(20)), Ok(()));
+        latch.wait();
+    }
+}
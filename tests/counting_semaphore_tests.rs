@@ -5,6 +5,7 @@ mod counting_semaphore_tests {
     use std::thread;
     use std::time::Duration;
     use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::error::AcquireError;
 
     //동시성 제어가 제대로 작동하는지 확인합니다. 최대 3개의 스레드만 동시에 임계 영역에 접근할 수 있어야 합니다.
     #[test]
@@ -121,4 +122,38 @@ mod counting_semaphore_tests {
 
         assert_eq!(*counter.lock().unwrap(), 3, "All three threads should have run");
     }
-}
\ No newline at end of file
+
+    // try_acquire와 acquire_timeout이 permit이 없을 때 블록되지 않고 오류를 돌려주는지 확인합니다.
+    #[test]
+    fn test_counting_semaphore_try_and_timeout() {
+        let semaphore = CountingSemaphore::new(1);
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+        assert_eq!(semaphore.acquire_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+
+        semaphore.release_many(2);
+        assert_eq!(semaphore.acquire_timeout(Duration::from_millis(20)), Ok(()));
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+    }
+
+    // 여러 permit을 한꺼번에 기다리는 스레드가 있어도 단일 permit 대기자가 wakeup을 잃지 않아야 합니다.
+    #[test]
+    fn test_counting_semaphore_acquire_many() {
+        let semaphore = Arc::new(CountingSemaphore::new(0));
+        let wide = {
+            let sem = Arc::clone(&semaphore);
+            thread::spawn(move || sem.acquire_many(3))
+        };
+        let narrow = {
+            let sem = Arc::clone(&semaphore);
+            thread::spawn(move || sem.acquire())
+        };
+        thread::sleep(Duration::from_millis(50));
+
+        semaphore.release();
+        narrow.join().unwrap();
+        semaphore.release_many(3);
+        wide.join().unwrap();
+        assert_eq!(semaphore.available_permits(), 0);
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod drain_tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::drain::DrainPolicy;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::priority_semaphore::PrioritySemaphore;
+    use concurrency_project::semaphore::strong_semaphore::StrongSemaphore;
+    use concurrency_project::semaphore::weak_semaphore::WeakSemaphore;
+
+    // drain은 나간 permit이 모두 돌아올 때까지 기다리고, 그동안 새 acquire는 reopen까지 기다려야 합니다.
+    #[test]
+    fn test_counting_semaphore_drain_waits_for_outstanding_permits() {
+        let semaphore = Arc::new(CountingSemaphore::new(3));
+        semaphore.acquire_many(2);
+
+        let drained = Arc::new(AtomicBool::new(false));
+        let drainer = {
+            let semaphore = Arc::clone(&semaphore);
+            let drained = Arc::clone(&drained);
+            thread::spawn(move || {
+                semaphore.drain();
+                drained.store(true, Ordering::SeqCst);
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+        assert!(semaphore.is_draining());
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+
+        let late = {
+            let semaphore = Arc::clone(&semaphore);
+            thread::spawn(move || {
+                semaphore.acquire();
+                semaphore.release();
+            })
+        };
+
+        semaphore.release();
+        thread::sleep(Duration::from_millis(20));
+        assert!(!drained.load(Ordering::SeqCst), "One permit is still outstanding");
+        semaphore.release();
+        drainer.join().unwrap();
+        assert!(drained.load(Ordering::SeqCst));
+        assert!(!late.is_finished(), "New acquires must wait while draining");
+
+        semaphore.reopen();
+        late.join().unwrap();
+        assert_eq!(semaphore.available_permits(), 3);
+    }
+
+    // Reject 정책에서는 대기 중이던 타임아웃 acquire와 새 try_acquire가 Draining으로 실패해야 하며, acquire()는 순서를 지켜 reopen을 기다립니다.
+    #[test]
+    fn test_strong_semaphore_reject_policy() {
+        let semaphore = Arc::new(StrongSemaphore::new(1).with_drain_policy(DrainPolicy::Reject));
+        semaphore.acquire();
+
+        let timed = {
+            let semaphore = Arc::clone(&semaphore);
+            thread::spawn(move || semaphore.acquire_timeout(Duration::from_secs(5)))
+        };
+        let blocking = {
+            let semaphore = Arc::clone(&semaphore);
+            thread::spawn(move || {
+                semaphore.acquire();
+                semaphore.release();
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+        assert_eq!(timed.join().unwrap(), Err(AcquireError::Draining));
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::Draining));
+
+        semaphore.release();
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(20)), Ok(()));
+        assert!(!blocking.is_finished());
+
+        semaphore.reopen();
+        blocking.join().unwrap();
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+        semaphore.release();
+    }
+
+    // permit이 하나도 나가 있지 않으면 drain은 바로 끝나고, reopen 후에는 다시 발급되어야 합니다.
+    #[test]
+    fn test_weak_semaphore_drain_and_reopen() {
+        let semaphore = WeakSemaphore::new(2);
+        semaphore.drain();
+        assert!(semaphore.is_draining());
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+
+        semaphore.reopen();
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(10)), Err(AcquireError::Timeout));
+        semaphore.release();
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(10)), Ok(()));
+    }
+
+    // PrioritySemaphore도 drain 중에는 실패할 수 있는 대기자를 Draining으로 돌려보내고, 남은 대기자는 reopen 뒤 priority 순으로 받아야 합니다.
+    #[test]
+    fn test_priority_semaphore_drain_keeps_priority_order() {
+        let semaphore = Arc::new(PrioritySemaphore::new(1).with_drain_policy(DrainPolicy::Reject));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        semaphore.acquire();
+
+        let timed = {
+            let semaphore = Arc::clone(&semaphore);
+            thread::spawn(move || semaphore.acquire_with_priority_timeout(5, Duration::from_secs(5)))
+        };
+        let mut handles = vec![];
+        for priority in [1, 9] {
+            let semaphore = Arc::clone(&semaphore);
+            let order = Arc::clone(&order);
+            handles.push(thread::spawn(move || {
+                semaphore.acquire_with_priority(priority);
+                order.lock().unwrap().push(priority);
+                semaphore.release();
+            }));
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+        assert_eq!(timed.join().unwrap(), Err(AcquireError::Draining));
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::Draining));
+
+        semaphore.release();
+        assert_eq!(semaphore.drain_timeout(Duration::from_millis(20)), Ok(()));
+        assert!(order.lock().unwrap().is_empty());
+        assert_eq!(semaphore.waiting_threads(), 2);
+
+        semaphore.reopen();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![9, 1]);
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod fair_share_semaphore_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::fair_share_semaphore::{FairShareSemaphore, TenantConfig};
+
+    // 시끄러운 tenant가 대기열을 먼저 채워도, permit은 weight 비율로 tenant들 사이를 번갈아 가야 합니다.
+    #[test]
+    fn test_fair_share_weighted_round_robin() {
+        let semaphore = Arc::new(FairShareSemaphore::new(1));
+        semaphore.configure_tenant("noisy", TenantConfig { weight: 1, max_in_flight: None });
+        semaphore.configure_tenant("quiet", TenantConfig { weight: 2, max_in_flight: None });
+        let order = Arc::new(Mutex::new(Vec::new()));
+        semaphore.acquire(&"noisy");
+
+        let mut handles = vec![];
+        for tenant in ["noisy"; 6].into_iter().chain(["quiet"; 4]) {
+            let sem = Arc::clone(&semaphore);
+            let order = Arc::clone(&order);
+            handles.push(thread::spawn(move || {
+                sem.acquire(&tenant);
+                order.lock().unwrap().push(tenant);
+                sem.release(&tenant);
+            }));
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        semaphore.release(&"noisy");
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let order = order.lock().unwrap();
+        assert_eq!(
+            *order,
+            vec!["noisy", "quiet", "quiet", "noisy", "quiet", "quiet", "noisy", "noisy", "noisy", "noisy"]
+        );
+        let quiet = semaphore.tenant_stats(&"quiet").unwrap();
+        assert_eq!(quiet.acquired, 4);
+        assert_eq!(quiet.in_flight, 0);
+    }
+
+    // tenant별 max_in_flight를 넘으면 남은 permit이 있어도 그 tenant는 기다려야 합니다.
+    #[test]
+    fn test_fair_share_per_tenant_cap() {
+        let semaphore = FairShareSemaphore::new(3);
+        semaphore.configure_tenant("a", TenantConfig { weight: 1, max_in_flight: Some(1) });
+
+        assert_eq!(semaphore.try_acquire(&"a"), Ok(()));
+        assert_eq!(semaphore.try_acquire(&"a"), Err(AcquireError::WouldBlock));
+        assert_eq!(semaphore.acquire_timeout(&"a", Duration::from_millis(20)), Err(AcquireError::Timeout));
+        assert_eq!(semaphore.try_acquire(&"b"), Ok(()));
+
+        let stats = semaphore.tenant_stats(&"a").unwrap();
+        assert_eq!((stats.in_flight, stats.acquired, stats.timed_out, stats.waiting), (1, 1, 1, 0));
+
+        semaphore.release(&"a");
+        assert_eq!(semaphore.try_acquire(&"a"), Ok(()));
+    }
+
+    // 타임아웃으로 대기열이 빈 tenant가 다시 줄을 서도, 한 라운드에 두 번 차례를 받으면 안 됩니다.
+    #[test]
+    fn test_fair_share_timeout_does_not_duplicate_turns() {
+        let semaphore = Arc::new(FairShareSemaphore::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        semaphore.acquire(&"b");
+        assert_eq!(semaphore.acquire_timeout(&"a", Duration::from_millis(20)), Err(AcquireError::Timeout));
+
+        let mut handles = vec![];
+        for tenant in ["a", "a", "b", "b"] {
+            let sem = Arc::clone(&semaphore);
+            let order = Arc::clone(&order);
+            handles.push(thread::spawn(move || {
+                sem.acquire(&tenant);
+                order.lock().unwrap().push(tenant);
+                sem.release(&tenant);
+            }));
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        semaphore.release(&"b");
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec!["a", "b", "a", "b"]);
+    }
+
+    // cap에 걸린 tenant의 대기자가 있어도, 남은 permit은 다른 tenant가 바로 가져갈 수 있어야 합니다.
+    #[test]
+    fn test_fair_share_capped_waiter_does_not_block_others() {
+        let semaphore = Arc::new(FairShareSemaphore::new(3));
+        semaphore.configure_tenant("a", TenantConfig { weight: 1, max_in_flight: Some(1) });
+        semaphore.acquire(&"a");
+
+        let sem = Arc::clone(&semaphore);
+        let waiter = thread::spawn(move || {
+            sem.acquire(&"a");
+            sem.release(&"a");
+        });
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(semaphore.tenant_stats(&"a").unwrap().waiting, 1);
+
+        assert_eq!(semaphore.try_acquire(&"b"), Ok(()));
+        assert_eq!(semaphore.acquire_timeout(&"c", Duration::from_millis(20)), Ok(()));
+        assert_eq!(semaphore.try_acquire(&"b"), Err(AcquireError::WouldBlock));
+
+        semaphore.release(&"a");
+        waiter.join().unwrap();
+        semaphore.release(&"b");
+        semaphore.release(&"c");
+    }
+
+    // 설정하지 않은 key로 들어온 tenant는 대기자와 나간 permit이 없어지면 정리되어, 설정한 tenant만 남아야 합니다.
+    #[test]
+    fn test_fair_share_forgets_idle_unconfigured_tenants() {
+        let semaphore = Arc::new(FairShareSemaphore::new(2));
+        semaphore.configure_tenant(0, TenantConfig { weight: 1, max_in_flight: Some(1) });
+
+        let handles: Vec<_> = (1..=20)
+            .map(|key| {
+                let sem = Arc::clone(&semaphore);
+                thread::spawn(move || {
+                    sem.acquire(&key);
+                    thread::sleep(Duration::from_millis(1));
+                    sem.release(&key);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(semaphore.tenant_count(), 1);
+
+        semaphore.acquire(&0);
+        semaphore.acquire(&21);
+        assert_eq!(semaphore.try_acquire(&22), Err(AcquireError::WouldBlock));
+        assert_eq!(semaphore.acquire_timeout(&23, Duration::from_millis(10)), Err(AcquireError::Timeout));
+        assert_eq!(semaphore.tenant_count(), 2);
+
+        semaphore.release(&21);
+        semaphore.release(&0);
+        assert_eq!(semaphore.tenant_count(), 1);
+        assert_eq!(semaphore.tenant_stats(&21), None);
+        assert_eq!(semaphore.tenant_stats(&0).unwrap().acquired, 1);
+    }
+}
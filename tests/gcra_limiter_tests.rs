@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod gcra_limiter_tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use concurrency_project::semaphore::clock::{Clock, ManualClock};
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::gcra_limiter::{GcraLimiter, KeyedGcraLimiter};
+    use concurrency_project::semaphore::permit::Limiter;
+
+    // burst만큼 즉시 허용한 뒤에는 emission interval마다 하나씩만 허용해야 합니다.
+    #[test]
+    fn test_gcra_burst_then_steady_rate() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = GcraLimiter::with_clock(10, 3, Arc::clone(&clock));
+
+        for _ in 0..3 {
+            assert_eq!(limiter.try_acquire(1), Ok(()));
+        }
+        assert_eq!(limiter.try_acquire(1), Err(AcquireError::WouldBlock));
+
+        clock.advance(Duration::from_millis(100));
+        assert_eq!(limiter.try_acquire(1), Ok(()));
+        assert_eq!(limiter.try_acquire(1), Err(AcquireError::WouldBlock));
+
+        let start = clock.now();
+        limiter.acquire(2);
+        assert_eq!(clock.now() - start, Duration::from_millis(200));
+        assert_eq!(limiter.acquire_timeout(1, Duration::from_millis(50)), Err(AcquireError::Timeout));
+    }
+
+    // 초당 10억 개를 넘는 rate에서도 간격이 0이 되지 않아 burst를 넘는 요청은 막혀야 합니다.
+    #[test]
+    fn test_gcra_rate_above_one_per_nanosecond() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = GcraLimiter::with_clock(u32::MAX, 4, Arc::clone(&clock));
+        for _ in 0..4 {
+            assert_eq!(limiter.try_acquire(1), Ok(()));
+        }
+        assert_eq!(limiter.try_acquire(1), Err(AcquireError::WouldBlock));
+
+        clock.advance(Duration::from_nanos(2));
+        assert_eq!(limiter.try_acquire(2), Ok(()));
+        assert_eq!(limiter.try_acquire(1), Err(AcquireError::WouldBlock));
+        assert_eq!(limiter.acquire_timeout(4, Duration::from_nanos(10)), Ok(()));
+    }
+
+    // 키마다 독립된 예산을 가지며, 만료된 키는 cleanup으로 정리되어야 합니다.
+    #[test]
+    fn test_keyed_gcra_independent_budgets() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = KeyedGcraLimiter::with_clock(1, 2, Arc::clone(&clock));
+
+        assert_eq!(limiter.try_acquire(&"alice", 2), Ok(()));
+        assert_eq!(limiter.try_acquire(&"alice", 1), Err(AcquireError::WouldBlock));
+        assert_eq!(limiter.try_acquire(&"bob", 1), Ok(()));
+        assert_eq!(limiter.len(), 2);
+
+        clock.advance(Duration::from_secs(1));
+        limiter.cleanup();
+        assert_eq!(limiter.len(), 1, "bob's budget is fully restored and can be forgotten");
+        assert_eq!(limiter.try_acquire(&"alice", 1), Ok(()));
+    }
+
+    fn run_with<L: Limiter>(limiter: &L) -> Vec<bool> {
+        (0..3).map(|_| limiter.try_acquire_permit(1).map(|p| p.forget()).is_ok()).collect()
+    }
+
+    // 동시성 제한과 속도 제한이 같은 Limiter 인터페이스로 교체 가능한지 확인합니다.
+    #[test]
+    fn test_limiters_are_interchangeable() {
+        let semaphore = CountingSemaphore::new(2);
+        let gcra = GcraLimiter::with_clock(1, 2, ManualClock::new());
+        assert_eq!(run_with(&semaphore), vec![true, true, false]);
+        assert_eq!(run_with(&gcra), vec![true, true, false]);
+
+        // 세마포어 permit은 drop 시 돌아오고, 속도 제한 permit은 소비됩니다.
+        let semaphore = CountingSemaphore::new(1);
+        drop(semaphore.acquire_permit(1));
+        assert_eq!(semaphore.available_permits(), 1);
+        assert_eq!(gcra.acquire_permit_timeout(1, Duration::ZERO).unwrap_err(), AcquireError::Timeout);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod hierarchical_semaphore_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::hierarchical_semaphore::HierarchicalSemaphore;
+
+    // 자식 한도와 부모 한도가 동시에 지켜져야 합니다.
+    #[test]
+    fn test_hierarchical_limits_are_nested() {
+        let global = HierarchicalSemaphore::new(3);
+        let children = [global.child(2), global.child(2)];
+        let in_use = Arc::new(Mutex::new((0, [0, 0])));
+        let mut handles = vec![];
+
+        for i in 0..40 {
+            let child = Arc::clone(&children[i % 2]);
+            let in_use = Arc::clone(&in_use);
+            handles.push(thread::spawn(move || {
+                child.acquire();
+                {
+                    let mut in_use = in_use.lock().unwrap();
+                    in_use.0 += 1;
+                    in_use.1[i % 2] += 1;
+                    assert!(in_use.0 <= 3, "Global limit exceeded");
+                    assert!(in_use.1[i % 2] <= 2, "Child limit exceeded");
+                }
+                thread::sleep(Duration::from_millis(2));
+                {
+                    let mut in_use = in_use.lock().unwrap();
+                    in_use.0 -= 1;
+                    in_use.1[i % 2] -= 1;
+                }
+                child.release();
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(global.available_permits(), 3);
+    }
+
+    // 임의의 깊이에서, 실패한 획득은 이미 잡은 조상의 permit을 되돌려야 합니다.
+    #[test]
+    fn test_hierarchical_arbitrary_depth_rollback() {
+        let root = HierarchicalSemaphore::new(1);
+        let mid = root.child(5);
+        let leaf = mid.child(5);
+        let sibling = root.child(5);
+
+        assert_eq!(leaf.try_acquire(), Ok(()));
+        assert_eq!((root.available_permits(), mid.available_permits(), leaf.available_permits()), (0, 4, 4));
+
+        assert_eq!(sibling.try_acquire(), Err(AcquireError::WouldBlock));
+        assert_eq!(leaf.acquire_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+        assert_eq!((mid.available_permits(), leaf.available_permits(), sibling.available_permits()), (4, 4, 5));
+
+        leaf.release();
+        assert_eq!(sibling.try_acquire(), Ok(()));
+    }
+}
@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod introspection_tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::binary_semaphore::BinarySemaphore;
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::fair_share_semaphore::FairShareSemaphore;
+    use concurrency_project::semaphore::hierarchical_semaphore::HierarchicalSemaphore;
+    use concurrency_project::semaphore::keyed_semaphore::KeyedSemaphore;
+    use concurrency_project::semaphore::priority_semaphore::PrioritySemaphore;
+    use concurrency_project::semaphore::reentrant_semaphore::ReentrantSemaphore;
+    use concurrency_project::semaphore::strong_semaphore::StrongSemaphore;
+    use concurrency_project::semaphore::weak_semaphore::WeakSemaphore;
+
+    // queue_snapshot은 대기 순서대로 스레드 이름, label, 대기 시간을 보여줘야 합니다.
+    #[test]
+    fn test_strong_semaphore_queue_snapshot() {
+        let semaphore = Arc::new(StrongSemaphore::new(1));
+        semaphore.acquire();
+
+        let mut handles = vec![];
+        for (name, label) in [("worker-a", Some("flush")), ("worker-b", None)] {
+            let semaphore = Arc::clone(&semaphore);
+            handles.push(
+                thread::Builder::new()
+                    .name(name.into())
+                    .spawn(move || {
+                        match label {
+                            Some(label) => semaphore.acquire_labeled(label),
+                            None => semaphore.acquire(),
+                        }
+                        semaphore.release();
+                    })
+                    .unwrap(),
+            );
+            thread::sleep(Duration::from_millis(20));
+        }
+
+        let snapshot = semaphore.queue_snapshot();
+        assert_eq!(semaphore.waiting_threads(), 2);
+        assert_eq!(semaphore.available_permits(), 0);
+        assert_eq!(snapshot[0].thread_name.as_deref(), Some("worker-a"));
+        assert_eq!(snapshot[0].label.as_deref(), Some("flush"));
+        assert_eq!(snapshot[1].thread_name.as_deref(), Some("worker-b"));
+        assert_eq!(snapshot[1].label, None);
+        assert_eq!(snapshot[0].thread_id, handles[0].thread().id());
+        assert!(snapshot[0].waited > snapshot[1].waited);
+
+        let debug = format!("{:?}", semaphore);
+        assert!(debug.contains("waiting_threads: 2") && debug.contains("worker-a"), "{}", debug);
+
+        semaphore.release();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(semaphore.queue_snapshot().is_empty());
+    }
+
+    // 다른 세마포어들도 남은 permit과 대기 스레드 수를 보여주고 Debug로 출력되어야 합니다.
+    #[test]
+    fn test_waiting_threads_and_debug_output() {
+        let counting = Arc::new(CountingSemaphore::new(1));
+        let binary = Arc::new(BinarySemaphore::new());
+        let weak = Arc::new(WeakSemaphore::new(1));
+        counting.acquire();
+        binary.acquire();
+        weak.acquire();
+
+        let handles = vec![
+            {
+                let counting = Arc::clone(&counting);
+                thread::spawn(move || {
+                    counting.acquire_many(1);
+                    counting.release();
+                })
+            },
+            {
+                let binary = Arc::clone(&binary);
+                thread::spawn(move || {
+                    binary.acquire();
+                    binary.release();
+                })
+            },
+            {
+                let weak = Arc::clone(&weak);
+                thread::spawn(move || {
+                    weak.acquire();
+                    weak.release();
+                })
+            },
+        ];
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!((counting.available_permits(), counting.waiting_threads()), (0, 1));
+        assert_eq!((binary.available_permits(), binary.waiting_threads()), (0, 1));
+        assert_eq!((weak.available_permits(), weak.waiting_threads()), (0, 1));
+        assert_eq!(
+            format!("{:?}", counting),
+            "CountingSemaphore { available_permits: 0, waiting_threads: 1, draining: false, paused: false }"
+        );
+        assert_eq!(format!("{:?}", binary), "BinarySemaphore { available_permits: 0, waiting_threads: 1 }");
+        let debug = format!("{:?}", weak);
+        assert!(
+            debug.starts_with("WeakSemaphore { available_permits: 0, waiting_threads: 1, draining: false, paused: false"),
+            "{}",
+            debug
+        );
+
+        counting.release();
+        binary.release();
+        weak.release();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!((counting.available_permits(), counting.waiting_threads()), (1, 0));
+        assert_eq!((binary.available_permits(), binary.waiting_threads()), (1, 0));
+        assert_eq!((weak.available_permits(), weak.waiting_threads()), (1, 0));
+    }
+
+    // PrioritySemaphore도 대기 스레드 수를 세고 Debug에 남은 permit과 aging 설정을 보여줘야 합니다.
+    #[test]
+    fn test_priority_semaphore_introspection() {
+        let semaphore = Arc::new(PrioritySemaphore::with_aging(1, Duration::from_secs(1)));
+        semaphore.acquire();
+        let waiter = {
+            let semaphore = Arc::clone(&semaphore);
+            thread::spawn(move || {
+                semaphore.acquire_with_priority(3);
+                semaphore.release();
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!((semaphore.available_permits(), semaphore.waiting_threads()), (0, 1));
+        assert_eq!(
+            format!("{:?}", semaphore),
+            "PrioritySemaphore { available_permits: 0, waiting_threads: 1, aging: Some(1s), draining: false }"
+        );
+
+        semaphore.release();
+        waiter.join().unwrap();
+        assert_eq!((semaphore.available_permits(), semaphore.waiting_threads()), (1, 0));
+    }
+
+    // 조합형 세마포어들도 남은 permit과 대기 스레드 수를 보여주고 Debug로 출력되어야 합니다.
+    #[test]
+    fn test_composite_semaphores_introspection() {
+        let fair = Arc::new(FairShareSemaphore::new(1));
+        let keyed = Arc::new(KeyedSemaphore::with_global_limit(1, 2));
+        let root = HierarchicalSemaphore::new(2);
+        let child = root.child(1);
+        let reentrant = Arc::new(ReentrantSemaphore::new());
+
+        fair.acquire(&"a");
+        let permit = keyed.acquire(&"k");
+        child.acquire();
+        reentrant.acquire();
+        reentrant.acquire();
+
+        let handles = vec![
+            {
+                let fair = Arc::clone(&fair);
+                thread::spawn(move || {
+                    fair.acquire(&"b");
+                    fair.release(&"b");
+                })
+            },
+            {
+                let keyed = Arc::clone(&keyed);
+                thread::spawn(move || drop(keyed.acquire(&"k")))
+            },
+            {
+                let child = Arc::clone(&child);
+                thread::spawn(move || {
+                    child.acquire();
+                    child.release();
+                })
+            },
+            {
+                let reentrant = Arc::clone(&reentrant);
+                thread::spawn(move || {
+                    reentrant.acquire();
+                    reentrant.release().unwrap();
+                })
+            },
+        ];
+        thread::sleep(Duration::from_millis(20));
+
+        assert_eq!((fair.available_permits(), fair.waiting_threads()), (0, 1));
+        assert_eq!(
+            format!("{:?}", fair),
+            "FairShareSemaphore { available_permits: 0, waiting_threads: 1, tenants: 2, active_tenants: 1 }"
+        );
+        assert_eq!((keyed.available_permits(&"k"), keyed.waiting_threads(&"k")), (0, 1));
+        assert_eq!(keyed.global_available_permits(), Some(1));
+        assert_eq!(
+            format!("{:?}", keyed),
+            "KeyedSemaphore { keys: 1, default_limit: 1, waiting_threads: 1, global_available_permits: Some(1) }"
+        );
+        assert_eq!((child.available_permits(), child.waiting_threads()), (0, 1));
+        assert_eq!((root.available_permits(), root.waiting_threads()), (1, 0));
+        assert_eq!(
+            format!("{:?}", child),
+            "HierarchicalSemaphore { limit: 1, available_permits: 0, waiting_threads: 1, depth: 1 }"
+        );
+        assert_eq!((reentrant.available_permits(), reentrant.waiting_threads()), (0, 1));
+        let debug = format!("{:?}", reentrant);
+        assert!(debug.contains("waiting_threads: 1") && debug.contains("holds: 2"), "{}", debug);
+
+        fair.release(&"a");
+        drop(permit);
+        child.release();
+        reentrant.release().unwrap();
+        reentrant.release().unwrap();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!((fair.available_permits(), fair.waiting_threads()), (1, 0));
+        assert_eq!(keyed.global_available_permits(), Some(2));
+        assert_eq!((root.available_permits(), child.available_permits()), (2, 1));
+        assert_eq!((reentrant.available_permits(), reentrant.waiting_threads()), (1, 0));
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod keyed_semaphore_tests {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::keyed_semaphore::KeyedSemaphore;
+
+    // 키마다 독립적으로 동시 실행 수가 제한되어야 합니다.
+    #[test]
+    fn test_keyed_semaphore_per_key_limit() {
+        let semaphore = Arc::new(KeyedSemaphore::new(2));
+        let in_use = Arc::new(Mutex::new(HashMap::new()));
+        let mut handles = vec![];
+
+        for i in 0..30 {
+            let sem = Arc::clone(&semaphore);
+            let in_use = Arc::clone(&in_use);
+            handles.push(thread::spawn(move || {
+                let key = i % 3;
+                let _permit = sem.acquire(&key);
+                {
+                    let mut in_use = in_use.lock().unwrap();
+                    let count = in_use.entry(key).or_insert(0);
+                    *count += 1;
+                    assert!(*count <= 2, "More than two holders for key {}", key);
+                }
+                thread::sleep(Duration::from_millis(5));
+                *in_use.lock().unwrap().get_mut(&key).unwrap() -= 1;
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(semaphore.is_empty(), "Idle keys should be garbage-collected");
+    }
+
+    // 키별 한도를 따로 줄 수 있고, 모든 permit이 반납되면 항목이 정리되어야 합니다.
+    #[test]
+    fn test_keyed_semaphore_overrides_and_gc() {
+        let semaphore = KeyedSemaphore::new(1);
+        semaphore.set_limit("host-a", 2);
+
+        let a1 = semaphore.try_acquire(&"host-a").unwrap();
+        let _a2 = semaphore.try_acquire(&"host-a").unwrap();
+        assert_eq!(semaphore.try_acquire(&"host-a").err(), Some(AcquireError::WouldBlock));
+        let b = semaphore.try_acquire(&"host-b").unwrap();
+        assert_eq!(semaphore.acquire_timeout(&"host-b", Duration::from_millis(20)).err(), Some(AcquireError::Timeout));
+        assert_eq!(semaphore.len(), 2);
+
+        drop(b);
+        assert_eq!(semaphore.len(), 1);
+        drop(a1);
+        assert_eq!(semaphore.available_permits(&"host-a"), 1);
+    }
+
+    // 전체 한도는 키별 한도보다 먼저 걸릴 수 있어야 합니다.
+    #[test]
+    fn test_keyed_semaphore_global_limit() {
+        let semaphore = KeyedSemaphore::with_global_limit(5, 2);
+        let _a = semaphore.acquire(&1);
+        let _b = semaphore.acquire(&2);
+        assert_eq!(semaphore.try_acquire(&3).err(), Some(AcquireError::WouldBlock));
+        assert_eq!(semaphore.len(), 2, "A failed acquire must not leave an entry behind");
+    }
+}
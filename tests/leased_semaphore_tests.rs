@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod leased_semaphore_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::clock::ManualClock;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::leased_semaphore::{LeaseExpired, LeasedSemaphore};
+
+    // 만료된 lease는 reap으로 회수되고, 그 뒤의 늦은 반납은 permit을 다시 돌려주지 않아야 합니다.
+    #[test]
+    fn test_lease_expires_and_late_release_is_ignored() {
+        let clock = Arc::new(ManualClock::new());
+        let semaphore = LeasedSemaphore::with_clock(1, Arc::clone(&clock));
+
+        let lease = thread::scope(|s| {
+            thread::Builder::new()
+                .name("stuck-worker".into())
+                .spawn_scoped(s, || semaphore.acquire(Duration::from_secs(1)))
+                .unwrap()
+                .join()
+                .unwrap()
+        });
+        assert_eq!(semaphore.try_acquire(Duration::from_secs(1)).err(), Some(AcquireError::WouldBlock));
+
+        clock.advance(Duration::from_secs(2));
+        let expired = semaphore.reap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].thread_name.as_deref(), Some("stuck-worker"));
+        assert_eq!(expired[0].expired_at, Duration::from_secs(1));
+
+        assert_eq!(lease.renew(Duration::from_secs(1)), Err(LeaseExpired));
+        assert_eq!(lease.release(), Err(LeaseExpired));
+        assert_eq!(semaphore.late_releases(), 1);
+        assert_eq!(semaphore.available_permits(), 1, "The permit must be returned exactly once");
+    }
+
+    // 갱신한 lease는 원래 TTL이 지나도 회수되지 않아야 합니다.
+    #[test]
+    fn test_lease_renewal() {
+        let clock = Arc::new(ManualClock::new());
+        let semaphore = LeasedSemaphore::with_clock(1, Arc::clone(&clock));
+        let lease = semaphore.acquire(Duration::from_secs(1));
+
+        clock.advance(Duration::from_millis(800));
+        assert_eq!(lease.renew(Duration::from_secs(1)), Ok(()));
+        clock.advance(Duration::from_millis(800));
+        assert!(semaphore.reap().is_empty());
+
+        // 만료된 permit은 try_acquire가 스스로 회수해서 쓸 수 있습니다.
+        clock.advance(Duration::from_secs(1));
+        let next = semaphore.try_acquire(Duration::from_secs(1)).unwrap();
+        assert_eq!(semaphore.active_leases(), 1);
+        drop(lease);
+        drop(next);
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    // 백그라운드 reaper가 만료된 permit을 회수해서 블록된 스레드를 깨우고 만료를 보고해야 합니다.
+    #[test]
+    fn test_lease_background_reaper() {
+        let semaphore = Arc::new(LeasedSemaphore::new(1));
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let _reaper = {
+            let reported = Arc::clone(&reported);
+            semaphore.spawn_reaper(Duration::from_millis(5), move |lease| {
+                reported.lock().unwrap().push(lease.id);
+            })
+        };
+
+        let hung = semaphore.acquire(Duration::from_millis(30));
+        let waiter = semaphore.acquire(Duration::from_secs(5));
+        assert_eq!(*reported.lock().unwrap(), vec![hung.id()]);
+        drop(waiter);
+        drop(hung);
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod multi_acquire_tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::multi_acquire::{
+        acquire_all, acquire_all_timeout, release_all, try_acquire_all,
+    };
+
+    // 서로 반대 순서로 두 세마포어를 요청하는 스레드들이 교착 없이 끝나야 합니다.
+    #[test]
+    fn test_acquire_all_opposite_orders_no_deadlock() {
+        let a = Arc::new(CountingSemaphore::new(1));
+        let b = Arc::new(CountingSemaphore::new(1));
+        let mut handles = vec![];
+
+        for i in 0..20 {
+            let a = Arc::clone(&a);
+            let b = Arc::clone(&b);
+            handles.push(thread::spawn(move || {
+                for _ in 0..50 {
+                    let set = if i % 2 == 0 { [&*a, &*b] } else { [&*b, &*a] };
+                    acquire_all(&set);
+                    release_all(&set);
+                }
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!((a.available_permits(), b.available_permits()), (1, 1));
+    }
+
+    // 하나라도 얻지 못하면 이미 얻은 permit을 모두 되돌려야 합니다.
+    #[test]
+    fn test_acquire_all_is_all_or_nothing() {
+        let a = CountingSemaphore::new(1);
+        let b = CountingSemaphore::new(0);
+        let c = CountingSemaphore::new(2);
+
+        assert_eq!(try_acquire_all(&[&a, &b, &c]), Err(AcquireError::WouldBlock));
+        assert_eq!(acquire_all_timeout(&[&c, &b, &a], Duration::from_millis(20)), Err(AcquireError::Timeout));
+        assert_eq!((a.available_permits(), b.available_permits(), c.available_permits()), (1, 0, 2));
+
+        b.release();
+        assert_eq!(try_acquire_all(&[&a, &b, &c]), Ok(()));
+        assert_eq!((a.available_permits(), b.available_permits(), c.available_permits()), (0, 0, 1));
+    }
+
+    // 같은 세마포어가 여러 번 나오면 그만큼 permit을 가져가야 합니다.
+    #[test]
+    fn test_acquire_all_duplicates() {
+        let a = CountingSemaphore::new(2);
+        assert_eq!(try_acquire_all(&[&a, &a, &a]), Err(AcquireError::WouldBlock));
+        assert_eq!(a.available_permits(), 2);
+        assert_eq!(try_acquire_all(&[&a, &a]), Ok(()));
+        assert_eq!(a.available_permits(), 0);
+    }
+
+    // 같은 세마포어를 두 번 요청한 스레드는 일부 permit을 쥔 채 기다리지 않고, 한 번에 모두 얻어야 합니다.
+    #[test]
+    fn test_acquire_all_duplicates_do_not_hold_and_wait() {
+        let a = Arc::new(CountingSemaphore::new(2));
+        a.acquire();
+
+        let sem = Arc::clone(&a);
+        let handle = thread::spawn(move || {
+            acquire_all(&[&sem, &sem]);
+            release_all(&[&sem, &sem]);
+        });
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(a.available_permits(), 1);
+
+        a.release();
+        handle.join().unwrap();
+        assert_eq!(a.available_permits(), 2);
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod object_pool_tests {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::object_pool::ObjectPool;
+
+    // 동시에 대여되는 객체 수가 max_size를 넘지 않고, 객체는 필요한 만큼만 만들어져야 합니다.
+    #[test]
+    fn test_object_pool_bounds_and_reuse() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = {
+            let created = Arc::clone(&created);
+            Arc::new(ObjectPool::new(3, move || {
+                created.fetch_add(1, Ordering::SeqCst);
+                Vec::<u8>::new()
+            }))
+        };
+        let in_use = Arc::new(Mutex::new(0));
+        let mut handles = vec![];
+
+        for _ in 0..20 {
+            let pool = Arc::clone(&pool);
+            let in_use = Arc::clone(&in_use);
+            handles.push(thread::spawn(move || {
+                let mut buffer = pool.get();
+                *in_use.lock().unwrap() += 1;
+                assert!(*in_use.lock().unwrap() <= 3, "More than three objects checked out");
+                buffer.push(1);
+                thread::sleep(Duration::from_millis(5));
+                *in_use.lock().unwrap() -= 1;
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(created.load(Ordering::SeqCst) <= 3);
+        assert_eq!(pool.available(), 3);
+    }
+
+    // 대여/반납 시 검증에 실패한 객체는 버려지고 새 객체로 대체되어야 합니다.
+    #[test]
+    fn test_object_pool_validation() {
+        let next = AtomicUsize::new(0);
+        let pool = ObjectPool::new(1, move || next.fetch_add(1, Ordering::SeqCst))
+            .with_checkout_validation(|id| *id != 1)
+            .with_return_validation(|id| *id % 2 == 0);
+
+        assert_eq!(*pool.get(), 0);
+        assert_eq!(pool.idle_count(), 1);
+        {
+            let mut object = pool.get();
+            assert_eq!(*object, 0, "Valid idle object should be reused");
+            *object = 1;
+        }
+        assert_eq!(pool.idle_count(), 0, "Odd objects fail return validation");
+        assert_eq!(*pool.get(), 1, "Fresh objects are not re-validated");
+
+        let object = pool.get();
+        assert_eq!(pool.try_get().err(), Some(AcquireError::WouldBlock));
+        assert_eq!(pool.get_timeout(Duration::from_millis(10)).err(), Some(AcquireError::Timeout));
+        object.discard();
+        assert_eq!(pool.idle_count(), 0);
+        assert_eq!(pool.available(), 1);
+    }
+
+    // idle_timeout보다 오래 쉰 객체는 제거되어야 합니다.
+    #[test]
+    fn test_object_pool_idle_eviction() {
+        let pool = ObjectPool::new(2, || 0).with_idle_timeout(Duration::from_millis(20));
+        drop((pool.get(), pool.get()));
+        assert_eq!(pool.idle_count(), 2);
+
+        thread::sleep(Duration::from_millis(30));
+        assert_eq!(pool.evict_idle(), 2);
+        assert_eq!(pool.idle_count(), 0);
+    }
+
+    // factory나 validator가 panic해도 permit은 돌아와서 pool 크기가 줄지 않아야 합니다.
+    #[test]
+    fn test_object_pool_panicking_factory_returns_permit() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let pool = {
+            let calls = Arc::clone(&calls);
+            ObjectPool::new(1, move || {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("factory failed");
+                }
+                7
+            })
+            .with_return_validation(|object| {
+                assert_ne!(*object, 0, "return validator failed");
+                true
+            })
+        };
+
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(pool.get()))).is_err());
+        assert_eq!(pool.available(), 1);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut object = pool.get();
+            *object = 0;
+        }));
+        assert!(result.is_err());
+        assert_eq!(pool.available(), 1);
+        assert_eq!(*pool.try_get().unwrap(), 7);
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod ownership_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use concurrency_project::semaphore::ownership::{
+        scoped_reporter, Misuse, MisuseReport, OwnershipTracker, Reporter,
+    };
+
+    fn drain(reports: &Arc<Mutex<Vec<MisuseReport>>>) -> Vec<Misuse> {
+        reports.lock().unwrap().drain(..).map(|r| r.misuse).collect()
+    }
+
+    // 아무도 permit을 들고 있지 않을 때의 release와, 다른 스레드의 permit을 대신 release하는 경우를 구분해서 보고해야 합니다.
+    #[test]
+    fn test_tracker_reports_bad_releases() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let tracker = Arc::new(OwnershipTracker::with_reporter("TestSemaphore", Reporter::Collect(Arc::clone(&reports))));
+
+        tracker.on_release(1);
+        assert_eq!(drain(&reports), vec![Misuse::ReleaseWithoutAcquire]);
+
+        tracker.on_acquire(2);
+        assert_eq!(tracker.held_by_current_thread(), 2);
+        {
+            let tracker = Arc::clone(&tracker);
+            thread::spawn(move || tracker.on_release(1)).join().unwrap();
+        }
+        let collected = reports.lock().unwrap().clone();
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].misuse, Misuse::ReleaseFromNonHolder);
+        assert!(collected[0].semaphore.starts_with("TestSemaphore#"));
+        assert!(!collected[0].backtrace.is_empty());
+        reports.lock().unwrap().clear();
+
+        tracker.on_release(1);
+        assert!(drain(&reports).is_empty());
+        assert_eq!(tracker.held_by_current_thread(), 0);
+    }
+
+    // 스레드가 permit을 든 채로 종료되면 남은 permit 수와 함께 보고해야 합니다.
+    #[test]
+    fn test_tracker_reports_permits_held_at_thread_exit() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let tracker = Arc::new(OwnershipTracker::with_reporter("TestSemaphore", Reporter::Collect(Arc::clone(&reports))));
+        {
+            let tracker = Arc::clone(&tracker);
+            thread::Builder::new()
+                .name("leaky".into())
+                .spawn(move || tracker.on_acquire(3))
+                .unwrap()
+                .join()
+                .unwrap();
+        }
+
+        let collected = reports.lock().unwrap().clone();
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].misuse, Misuse::HeldAtThreadExit { permits: 3 });
+        assert_eq!(collected[0].thread_name.as_deref(), Some("leaky"));
+    }
+
+    // 꺼진 tracker는 잘못된 release가 와도 아무것도 보고하거나 기록하지 않아야 합니다.
+    #[test]
+    fn test_disabled_tracker_is_a_no_op() {
+        let tracker = OwnershipTracker::disabled();
+        tracker.on_release(5);
+        tracker.on_acquire(1);
+        assert!(!tracker.is_enabled());
+        assert_eq!(tracker.held_by_current_thread(), 0);
+    }
+
+    // checked feature가 켜져 있으면 세마포어가 자동으로 소유권을 추적해야 합니다.
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_checked_counting_semaphore_reports_misuse() {
+        use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let _guard = scoped_reporter(Reporter::Collect(Arc::clone(&reports)));
+
+        let semaphore = CountingSemaphore::new(1);
+        semaphore.acquire();
+        semaphore.release();
+        semaphore.release();
+        assert_eq!(drain(&reports), vec![Misuse::ReleaseWithoutAcquire]);
+    }
+
+    // Panic reporter를 골랐다면 release는 permit을 돌려준 뒤에 panic해서, 세마포어는 계속 쓸 수 있어야 합니다.
+    #[cfg(feature = "checked")]
+    #[test]
+    fn test_panic_reporter_runs_after_permit_is_returned() {
+        use std::panic::{self, AssertUnwindSafe};
+        use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+
+        let semaphore = CountingSemaphore::new(0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _guard = scoped_reporter(Reporter::Panic);
+            semaphore.release();
+        }));
+        assert!(result.is_err());
+        assert_eq!(semaphore.available_permits(), 1);
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+    }
+
+    // scoped reporter는 guard가 사라지면 이전 reporter로 돌아가야 합니다.
+    #[test]
+    fn test_scoped_reporter_is_restored() {
+        let outer = Arc::new(Mutex::new(Vec::new()));
+        let inner = Arc::new(Mutex::new(Vec::new()));
+        let tracker = OwnershipTracker::enabled("TestSemaphore");
+
+        let _outer = scoped_reporter(Reporter::Collect(Arc::clone(&outer)));
+        {
+            let _inner = scoped_reporter(Reporter::Collect(Arc::clone(&inner)));
+            tracker.on_release(1);
+        }
+        tracker.on_release(1);
+        assert_eq!(drain(&inner), vec![Misuse::ReleaseWithoutAcquire]);
+        assert_eq!(drain(&outer), vec![Misuse::ReleaseWithoutAcquire]);
+    }
+}
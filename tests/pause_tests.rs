@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod pause_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::strong_semaphore::StrongSemaphore;
+
+    // pause 중에도 release는 받지만 새 permit은 내주지 않고, 막힌 스레드 수를 세어야 합니다.
+    #[test]
+    fn test_counting_semaphore_pause_blocks_new_acquirers() {
+        let semaphore = Arc::new(CountingSemaphore::new(1));
+        semaphore.acquire();
+        semaphore.pause();
+        assert!(semaphore.is_paused());
+
+        semaphore.release();
+        assert_eq!(semaphore.available_permits(), 1);
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+        assert_eq!(semaphore.acquire_timeout(Duration::from_millis(10)), Err(AcquireError::Timeout));
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let semaphore = Arc::clone(&semaphore);
+                thread::spawn(move || {
+                    semaphore.acquire();
+                    semaphore.release();
+                })
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(20));
+        assert!(handles.iter().all(|h| !h.is_finished()));
+        assert_eq!(semaphore.blocked_by_pause(), 3);
+
+        semaphore.resume();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(!semaphore.is_paused());
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    // pause 전후로 대기열에 들어온 스레드들은 resume 후에도 도착 순서대로 permit을 받아야 합니다.
+    #[test]
+    fn test_strong_semaphore_keeps_queue_order_across_pause() {
+        let semaphore = Arc::new(StrongSemaphore::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        semaphore.acquire();
+
+        let mut handles = vec![];
+        for i in 0..4 {
+            if i == 2 {
+                semaphore.pause();
+            }
+            let semaphore = Arc::clone(&semaphore);
+            let order = Arc::clone(&order);
+            handles.push(thread::spawn(move || {
+                semaphore.acquire();
+                order.lock().unwrap().push(i);
+                thread::sleep(Duration::from_millis(5));
+                semaphore.release();
+            }));
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        semaphore.release();
+        thread::sleep(Duration::from_millis(20));
+        assert!(order.lock().unwrap().is_empty(), "No permit should be granted while paused");
+        assert_eq!(semaphore.blocked_by_pause(), 2);
+
+        semaphore.resume();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+}
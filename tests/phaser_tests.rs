@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod phaser_tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::phaser::Phaser;
+
+    // 등록된 모든 party가 도착하면 phase가 진행되고, 각 스레드는 통과한 phase 번호를 돌려받아야 합니다.
+    #[test]
+    fn test_phaser_advances_phases() {
+        let num_threads = 4;
+        let phaser = Arc::new(Phaser::new(num_threads));
+        let mut handles = vec![];
+
+        for _ in 0..num_threads {
+            let phaser = Arc::clone(&phaser);
+            handles.push(thread::spawn(move || {
+                (0..3).map(|_| phaser.arrive_and_wait()).collect::<Vec<_>>()
+            }));
+        }
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), vec![0, 1, 2]);
+        }
+        assert_eq!(phaser.phase(), 3);
+    }
+
+    // 동적으로 등록/해제된 party 수에 맞춰 phase가 진행되어야 합니다.
+    #[test]
+    fn test_phaser_register_and_deregister() {
+        let phaser = Arc::new(Phaser::new(1));
+        assert_eq!(phaser.register(), 0);
+        assert_eq!(phaser.registered_parties(), 2);
+
+        let handle = {
+            let phaser = Arc::clone(&phaser);
+            thread::spawn(move || phaser.arrive_and_wait())
+        };
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(phaser.phase(), 0, "Phase should wait for the second party");
+
+        assert_eq!(phaser.arrive_and_deregister(), 0);
+        assert_eq!(handle.join().unwrap(), 0);
+        assert_eq!(phaser.phase(), 1);
+        assert_eq!(phaser.registered_parties(), 1);
+
+        assert_eq!(phaser.arrive(), 1);
+        assert_eq!(phaser.phase(), 2);
+    }
+
+    // 타임아웃되어도 도착은 기록되어 있어야 하며, 나머지 party가 도착하면 phase가 진행되어야 합니다.
+    #[test]
+    fn test_phaser_wait_timeout() {
+        let phaser = Phaser::new(2);
+        assert_eq!(phaser.arrive_and_wait_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+        assert_eq!(phaser.arrived_parties(), 1, "Arrival should survive the timeout");
+
+        assert_eq!(phaser.arrive(), 0);
+        assert_eq!(phaser.phase(), 1);
+    }
+
+    // 마지막 party가 해제되면 phase를 진행하지 않고 종료되어야 하며, 이후 도착은 기다리지 않아야 합니다.
+    #[test]
+    fn test_phaser_terminates_when_last_party_deregisters() {
+        let phaser = Phaser::new(2);
+        assert_eq!(phaser.arrive_and_deregister(), 0);
+        assert_eq!(phaser.phase(), 0);
+        assert!(!phaser.is_terminated());
+
+        assert_eq!(phaser.arrive_and_deregister(), 0);
+        assert!(phaser.is_terminated());
+        assert_eq!(phaser.phase(), 0, "Termination must not advance the phase");
+        assert_eq!(phaser.arrive_and_wait(), 0);
+        assert_eq!(phaser.arrive_and_wait_timeout(Duration::from_millis(10)), Ok(0));
+        assert_eq!(phaser.phase(), 0);
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod priority_semaphore_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::priority_semaphore::PrioritySemaphore;
+
+    fn run_waiters(semaphore: Arc<PrioritySemaphore>, priorities: &[u32]) -> Vec<usize> {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        semaphore.acquire();
+
+        let mut handles = vec![];
+        for (i, &priority) in priorities.iter().enumerate() {
+            let sem = Arc::clone(&semaphore);
+            let order = Arc::clone(&order);
+            handles.push(thread::spawn(move || {
+                sem.acquire_with_priority(priority);
+                order.lock().unwrap().push(i);
+                sem.release();
+            }));
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        semaphore.release();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let order = order.lock().unwrap().clone();
+        order
+    }
+
+    // 높은 priority가 먼저 처리되고, 같은 priority 안에서는 FIFO 순서가 지켜져야 합니다.
+    #[test]
+    fn test_priority_semaphore_order() {
+        let semaphore = Arc::new(PrioritySemaphore::new(1));
+        let order = run_waiters(semaphore, &[1, 5, 1, 5, 3]);
+        assert_eq!(order, vec![1, 3, 4, 0, 2]);
+    }
+
+    // aging이 켜져 있으면 오래 기다린 낮은 priority 대기자가 나중에 온 높은 priority보다 먼저 처리될 수 있어야 합니다.
+    #[test]
+    fn test_priority_semaphore_aging() {
+        let semaphore = Arc::new(PrioritySemaphore::with_aging(1, Duration::from_millis(10)));
+        let order = run_waiters(semaphore, &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+        assert_eq!(order[0], 0, "The oldest waiter has aged past priority 2");
+    }
+
+    // 타임아웃된 대기자는 heap에서 빠지고 permit을 가져가지 않아야 합니다.
+    #[test]
+    fn test_priority_semaphore_timeout() {
+        let semaphore = PrioritySemaphore::new(1);
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+        assert_eq!(
+            semaphore.acquire_with_priority_timeout(9, Duration::from_millis(20)),
+            Err(AcquireError::Timeout)
+        );
+        semaphore.release();
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+    }
+}
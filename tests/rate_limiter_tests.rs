@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod rate_limiter_tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use concurrency_project::semaphore::clock::{Clock, ManualClock};
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::rate_limiter::RateLimiter;
+
+    // burst만큼은 바로 쓸 수 있고, 그 이후에는 설정된 속도로만 토큰이 채워져야 합니다.
+    #[test]
+    fn test_rate_limiter_burst_and_refill() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = RateLimiter::with_clock(10, 5, Arc::clone(&clock));
+
+        for _ in 0..5 {
+            assert_eq!(limiter.try_acquire(1), Ok(()));
+        }
+        assert_eq!(limiter.try_acquire(1), Err(AcquireError::WouldBlock));
+
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(limiter.available_tokens(), 2);
+
+        // 오래 쉬어도 burst를 넘어서 쌓이지 않아야 합니다.
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(limiter.available_tokens(), 5);
+    }
+
+    // 초당 10억 개를 넘는 속도도 0ns 간격으로 나누지 않고 동작해야 합니다.
+    #[test]
+    fn test_rate_limiter_rate_above_one_per_nanosecond() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = RateLimiter::with_clock(u32::MAX, 4, Arc::clone(&clock));
+        for _ in 0..4 {
+            assert_eq!(limiter.try_acquire(1), Ok(()));
+        }
+        assert_eq!(limiter.try_acquire(1), Err(AcquireError::WouldBlock));
+
+        clock.advance(Duration::from_nanos(2));
+        assert_eq!(limiter.available_tokens(), 2);
+        assert_eq!(limiter.acquire_timeout(4, Duration::from_nanos(10)), Ok(()));
+    }
+
+    // 가중치가 있는 요청은 cost만큼의 토큰이 모일 때까지 (가짜 시계 위에서) 기다려야 합니다.
+    #[test]
+    fn test_rate_limiter_weighted_acquire_waits_for_refill() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = RateLimiter::with_clock(100, 10, Arc::clone(&clock));
+
+        limiter.acquire(10);
+        let start = clock.now();
+        limiter.acquire(4);
+        assert_eq!(clock.now() - start, Duration::from_millis(40));
+        assert_eq!(limiter.available_tokens(), 0);
+    }
+
+    // 마감 시간 안에 토큰이 채워질 수 없으면 타임아웃을 돌려줘야 합니다.
+    #[test]
+    fn test_rate_limiter_acquire_timeout() {
+        let clock = Arc::new(ManualClock::new());
+        let limiter = RateLimiter::with_clock(10, 3, Arc::clone(&clock));
+
+        limiter.acquire(3);
+        assert_eq!(limiter.acquire_timeout(2, Duration::from_millis(150)), Err(AcquireError::Timeout));
+        assert_eq!(clock.now(), Duration::ZERO, "Should fail without waiting");
+
+        assert_eq!(limiter.acquire_timeout(2, Duration::from_millis(200)), Ok(()));
+        assert_eq!(clock.now(), Duration::from_millis(200));
+    }
+
+    // 실제 시계로도 초당 속도가 대략 지켜지는지 확인합니다.
+    #[test]
+    fn test_rate_limiter_system_clock() {
+        let limiter = RateLimiter::new(100, 1);
+        let start = std::time::Instant::now();
+        for _ in 0..6 {
+            limiter.acquire(1);
+        }
+        assert!(start.elapsed() >= Duration::from_millis(45), "Limiter should throttle calls");
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod reentrant_semaphore_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::{AcquireError, ReleaseError};
+    use concurrency_project::semaphore::reentrant_semaphore::ReentrantSemaphore;
+
+    fn recurse(semaphore: &ReentrantSemaphore, depth: usize) -> usize {
+        semaphore.acquire();
+        let holds = if depth == 0 { semaphore.hold_count() } else { recurse(semaphore, depth - 1) };
+        semaphore.release().unwrap();
+        holds
+    }
+
+    // 소유 스레드의 재귀 호출은 스스로 교착에 빠지지 않고, 마지막 release에서만 풀려야 합니다.
+    #[test]
+    fn test_reentrant_semaphore_recursion() {
+        let semaphore = ReentrantSemaphore::new();
+        assert_eq!(recurse(&semaphore, 4), 5);
+        assert!(!semaphore.is_held_by_current_thread());
+        assert_eq!(semaphore.release(), Err(ReleaseError::NotHeld));
+    }
+
+    // 다른 스레드는 마지막 release 전까지 들어올 수 없고, 소유하지 않은 스레드의 release는 오류여야 합니다.
+    #[test]
+    fn test_reentrant_semaphore_excludes_other_threads() {
+        let semaphore = Arc::new(ReentrantSemaphore::new());
+        semaphore.acquire();
+        semaphore.acquire();
+
+        let other = {
+            let sem = Arc::clone(&semaphore);
+            thread::spawn(move || {
+                let released = sem.release();
+                let timed_out = sem.acquire_timeout(Duration::from_millis(20));
+                (released, timed_out)
+            })
+        };
+        assert_eq!(other.join().unwrap(), (Err(ReleaseError::NotOwner), Err(AcquireError::Timeout)));
+
+        semaphore.release().unwrap();
+        let still_locked = {
+            let sem = Arc::clone(&semaphore);
+            thread::spawn(move || sem.try_acquire())
+        };
+        assert_eq!(still_locked.join().unwrap(), Err(AcquireError::WouldBlock));
+
+        semaphore.release().unwrap();
+        let entered = Arc::new(Mutex::new(false));
+        let handle = {
+            let sem = Arc::clone(&semaphore);
+            let entered = Arc::clone(&entered);
+            thread::spawn(move || {
+                sem.acquire();
+                *entered.lock().unwrap() = true;
+                sem.release().unwrap();
+            })
+        };
+        handle.join().unwrap();
+        assert!(*entered.lock().unwrap());
+    }
+}
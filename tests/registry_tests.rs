@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod registry_tests {
+    use std::fs;
+    use concurrency_project::semaphore::builder::Fairness;
+    use concurrency_project::semaphore::config::{self, ConfigError, SemaphoreConfig};
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::registry::{self, Registry};
+
+    const CONFIG: &str = r#"
+# 쓰기는 순서대로 처리한다
+[[semaphore]]
+name = "db-writes"
+kind = "strong"
+permits = 4
+max = 8
+
+[[semaphore]]
+name = "reports"   # kind를 빼면 strong
+permits = 1
+"#;
+
+    // 설정 파일로 만든 세마포어를 이름으로 찾아 쓸 수 있고, dump에 현재 상태가 나와야 합니다.
+    #[test]
+    fn test_registry_from_toml_and_dump() {
+        let registry = Registry::from_toml(CONFIG).unwrap();
+        let writes = registry.get("db-writes").unwrap();
+        assert_eq!(writes.name(), Some("db-writes"));
+        assert!(registry.get("missing").is_none());
+
+        writes.acquire();
+        writes.acquire();
+        let states = registry.dump();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].name, "db-writes");
+        assert_eq!((states[0].permits, states[0].max_permits, states[0].available, states[0].held), (4, 8, 2, 2));
+        assert_eq!(states[1].kind, Fairness::Strong);
+        assert_eq!(
+            registry.to_string(),
+            "db-writes (Strong): permits=4/8 available=2 held=2 waiting=0\nreports (Strong): permits=1/1 available=1 held=0 waiting=0\n"
+        );
+        writes.release();
+        writes.release();
+    }
+
+    // reload는 나가 있는 permit을 건드리지 않고, 줄인 만큼은 돌아오는 permit에서 회수해야 합니다.
+    #[test]
+    fn test_registry_reload_keeps_held_permits() {
+        let registry = Registry::from_json(
+            r#"{"semaphores": [{"name": "db-writes", "kind": "weak", "permits": 4, "max": 8}]}"#,
+        )
+        .unwrap();
+        let writes = registry.get("db-writes").unwrap();
+        for _ in 0..3 {
+            writes.acquire();
+        }
+
+        registry.reload_json(r#"[{"name": "db-writes", "kind": "weak", "permits": 2, "max": 8}]"#).unwrap();
+        assert_eq!(writes.available_permits(), 0);
+        assert_eq!(writes.try_acquire(), Err(AcquireError::WouldBlock));
+        writes.release();
+        assert_eq!(writes.available_permits(), 0, "The first returned permit pays off the reduction");
+        writes.release();
+        writes.release();
+        assert_eq!(writes.available_permits(), 2);
+
+        let error = registry.reload_json(r#"[{"name": "db-writes", "kind": "priority", "permits": 5}]"#);
+        assert_eq!(error, Err(ConfigError::KindChanged("db-writes".into())));
+        assert_eq!(registry.dump()[0].permits, 2, "A rejected reload must not change anything");
+
+        registry.reload_json(r#"[{"name": "db-writes", "kind": "weak", "permits": 5, "max": 8}]"#).unwrap();
+        assert_eq!(writes.available_permits(), 5);
+        assert_eq!(writes.drain_timeout(std::time::Duration::from_millis(10)), Ok(()));
+    }
+
+    // 잘못된 설정은 줄 번호와 함께 거부되고, 파일 확장자에 따라 형식을 고르며, 전역 registry를 쓸 수 있어야 합니다.
+    #[test]
+    fn test_config_errors_file_loading_and_global() {
+        assert_eq!(
+            config::parse_toml("[[semaphore]]\nname = \"a\"\npermits = four\n"),
+            Err(ConfigError::Parse { line: 3, message: "invalid value four".into() })
+        );
+        assert_eq!(
+            config::parse_json(r#"[{"name": "a", "kind": "fifo", "permits": 1}]"#),
+            Err(ConfigError::UnknownKind("fifo".into()))
+        );
+        assert!(matches!(
+            Registry::from_toml("[[semaphore]]\nname = \"a\"\npermits = 3\nmax = 2\n"),
+            Err(ConfigError::Build { .. })
+        ));
+
+        let path = std::env::temp_dir().join(format!("semaphores-{}.json", std::process::id()));
+        fs::write(&path, r#"[{"name": "uploads", "kind": "priority", "permits": 2}]"#).unwrap();
+        let loaded = Registry::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap().dump()[0].kind, Fairness::Priority);
+
+        let config = SemaphoreConfig { name: "registry-tests".into(), kind: Fairness::Weak, permits: 1, max: None };
+        registry::global().register(&config).unwrap();
+        assert!(std::ptr::eq(registry::global(), registry::global()));
+        assert!(registry::global().get("registry-tests").is_some());
+        assert_eq!(registry::global().register(&config).err(), Some(ConfigError::Duplicate("registry-tests".into())));
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod select_tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::select::{select_acquire, select_acquire_timeout, try_select_acquire};
+
+    // 여러 풀 중 나중에 풀린 세마포어의 인덱스를 돌려주고, 하나의 permit만 가져가야 합니다.
+    #[test]
+    fn test_select_acquire_returns_released_index() {
+        let pools: Arc<Vec<CountingSemaphore>> = Arc::new((0..3).map(|_| CountingSemaphore::new(0)).collect());
+        let handle = {
+            let pools = Arc::clone(&pools);
+            thread::spawn(move || {
+                let refs: Vec<_> = pools.iter().collect();
+                select_acquire(&refs)
+            })
+        };
+
+        thread::sleep(Duration::from_millis(50));
+        pools[2].release();
+        assert_eq!(handle.join().unwrap(), 2);
+        assert!(pools.iter().all(|p| p.available_permits() == 0));
+    }
+
+    // 많은 release와 select가 경쟁해도 wakeup을 잃지 않고 permit 수가 보존되어야 합니다.
+    #[test]
+    fn test_select_acquire_no_lost_wakeups() {
+        let pools = Arc::new([CountingSemaphore::new(0), CountingSemaphore::new(0)]);
+        let rounds = 200;
+        let mut selectors = vec![];
+
+        for _ in 0..4 {
+            let pools = Arc::clone(&pools);
+            selectors.push(thread::spawn(move || {
+                for _ in 0..rounds {
+                    select_acquire(&[&pools[0], &pools[1]]);
+                }
+            }));
+        }
+
+        for i in 0..rounds * 4 {
+            pools[i % 2].release();
+        }
+
+        for handle in selectors {
+            handle.join().unwrap();
+        }
+        assert_eq!(pools[0].available_permits() + pools[1].available_permits(), 0);
+    }
+
+    // 바로 얻을 수 있는 세마포어가 없으면 try는 WouldBlock, timeout은 Timeout을 돌려줘야 합니다.
+    #[test]
+    fn test_select_acquire_try_and_timeout() {
+        let a = CountingSemaphore::new(0);
+        let b = CountingSemaphore::new(1);
+        assert_eq!(try_select_acquire(&[&a, &b]), Ok(1));
+        assert_eq!(try_select_acquire(&[&a, &b]), Err(AcquireError::WouldBlock));
+        assert_eq!(select_acquire_timeout(&[&a, &b], Duration::from_millis(20)), Err(AcquireError::Timeout));
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod sem_mutex_tests {
+    use std::sync::Arc;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::sem_mutex::SemMutex;
+
+    // 여러 스레드가 guard를 통해 데이터를 수정해도 갱신이 유실되지 않아야 합니다.
+    #[test]
+    fn test_sem_mutex_mutual_exclusion() {
+        let mutex = Arc::new(SemMutex::new(0));
+        let mut handles = vec![];
+
+        for _ in 0..10 {
+            let mutex = Arc::clone(&mutex);
+            handles.push(thread::spawn(move || {
+                for _ in 0..100 {
+                    *mutex.lock() += 1;
+                }
+            }));
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(Arc::try_unwrap(mutex).ok().unwrap().into_inner(), 1000);
+    }
+
+    // 이미 잠겨 있으면 try_lock은 WouldBlock, lock_timeout은 Timeout으로 실패하고, 풀린 뒤에는 얻을 수 있어야 합니다.
+    #[test]
+    fn test_sem_mutex_try_lock_and_timeout() {
+        let mutex = SemMutex::new(String::from("a"));
+        let guard = mutex.lock();
+        assert_eq!(mutex.try_lock().err(), Some(AcquireError::WouldBlock));
+        assert_eq!(mutex.lock_timeout(Duration::from_millis(20)).err(), Some(AcquireError::Timeout));
+        drop(guard);
+
+        mutex.try_lock().unwrap().push('b');
+        assert_eq!(*mutex.lock_timeout(Duration::from_millis(20)).unwrap(), "ab");
+    }
+
+    // 한 스레드가 잡은 잠금을 다른 스레드로 넘겨서, 받은 스레드가 수정하고 풀 수 있어야 합니다.
+    #[test]
+    fn test_sem_mutex_cross_thread_handoff() {
+        let mutex = SemMutex::new(vec![1]);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                let mut guard = mutex.lock();
+                guard.push(2);
+                tx.send(guard.into_handoff()).unwrap();
+            });
+            s.spawn(move || {
+                let mut guard = rx.recv().unwrap().into_guard();
+                guard.push(3);
+            });
+        });
+
+        assert_eq!(*mutex.try_lock().unwrap(), vec![1, 2, 3]);
+    }
+}
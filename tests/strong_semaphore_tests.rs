@@ -4,7 +4,8 @@ mod strong_semaphore_tests {
     use std::sync::{Arc, Mutex};
     use std::thread;
     use std::time::Duration;
-    use concurrency_project::semaphore::strong_semaphore::StrongSemaphore;
+    use concurrency_project::semaphore::error::AcquireError;
+    use concurrency_project::semaphore::strong_semaphore::{QueuePolicy, StrongSemaphore};
 
     #[test]
     fn test_strong_semaphore_concurrency() {
@@ -116,9 +117,58 @@ mod strong_semaphore_tests {
         handle.join().unwrap();
         assert_eq!(*flag.lock().unwrap(), true, "Semaphore should have been acquired and released");
     }
+
+    // 대기 시간이 계속 target을 넘는 과부하 상태에서는 head의 대기자 일부가 Shed로 실패해야 합니다.
+    #[test]
+    fn test_strong_semaphore_codel_sheds_under_overload() {
+        let policy = QueuePolicy::CoDel {
+            target: Duration::from_millis(5),
+            interval: Duration::from_millis(20),
+        };
+        let semaphore = Arc::new(StrongSemaphore::with_policy(1, policy));
+        semaphore.acquire();
+
+        let mut handles = vec![];
+        for _ in 0..12 {
+            let sem = Arc::clone(&semaphore);
+            handles.push(thread::spawn(move || {
+                let result = sem.acquire_or_shed();
+                if result.is_ok() {
+                    thread::sleep(Duration::from_millis(10));
+                    sem.release();
+                }
+                result
+            }));
+        }
+        thread::sleep(Duration::from_millis(60));
+        semaphore.release();
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        let shed = results.iter().filter(|r| **r == Err(AcquireError::Shed)).count();
+        println!("Shed {} of {} waiters", shed, results.len());
+        assert!(shed > 0, "Sustained queueing delay should shed some waiters");
+        assert!(shed < results.len(), "Some waiters should still be served");
+        assert_eq!(semaphore.shed_count(), shed);
+        assert_eq!(semaphore.try_acquire(), Ok(()), "Shed waiters must not leak permits");
+    }
+
+    // 정책이 없으면 아무도 shed되지 않고, 타임아웃된 대기자는 대기열에서 빠져야 합니다.
+    #[test]
+    fn test_strong_semaphore_timeout_without_policy() {
+        let semaphore = StrongSemaphore::new(1);
+        assert_eq!(semaphore.try_acquire(), Ok(()));
+        assert_eq!(semaphore.try_acquire(), Err(AcquireError::WouldBlock));
+        assert_eq!(semaphore.acquire_timeout(Duration::from_millis(20)), Err(AcquireError::Timeout));
+
+        semaphore.release();
+        assert_eq!(semaphore.acquire_or_shed(), Ok(()));
+        assert_eq!(semaphore.shed_count(), 0);
+    }
 }
 
 // test_strong_semaphore_concurrency: 동시성 제어가 제대로 작동하는지 확인합니다. 한 번에 하나의 스레드만 임계 영역에 접근할 수 있어야 합니다.
 // test_strong_semaphore_order: 강한 세마포어의 FIFO(First-In-First-Out) 특성을 검증합니다. 스레드들이 생성된 순서대로 세마포어를 획득해야 합니다.
 // test_strong_semaphore_multiple_permits: 여러 개의 허가(permit)를 가진 세마포어의 동작을 테스트합니다. 이 경우 최대 3개의 스레드가 동시에 임계 영역에 있을 수 있습니다.
-// test_strong_semaphore_zero_init: 0으로 초기화된 세마포어의 동작을 테스트합니다. 세마포어가 해제되기 전까지 스레드가 블록되어야 합니다.
\ No newline at end of file
+// test_strong_semaphore_zero_init: 0으로 초기화된 세마포어의 동작을 테스트합니다. 세마포어가 해제되기 전까지 스레드가 블록되어야 합니다.
+// test_strong_semaphore_codel_sheds_under_overload: CoDel 정책에서 지속적인 대기 지연이 있으면 head의 대기자가 Shed로 실패하는지 확인합니다.
+// test_strong_semaphore_timeout_without_policy: 정책이 없으면 아무도 shed되지 않고, 타임아웃된 대기자가 대기열에서 빠지는지 확인합니다.
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod watchdog_tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use concurrency_project::semaphore::binary_semaphore::BinarySemaphore;
+    use concurrency_project::semaphore::counting_semaphore::CountingSemaphore;
+    use concurrency_project::semaphore::strong_semaphore::StrongSemaphore;
+    use concurrency_project::semaphore::watchdog::{HoldReport, Watchdog};
+
+    fn collecting_watchdog(threshold_ms: u64) -> (Watchdog, Arc<Mutex<Vec<HoldReport>>>) {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let watchdog = {
+            let reports = Arc::clone(&reports);
+            Watchdog::new(Duration::from_millis(threshold_ms), Duration::from_millis(2), move |report| {
+                reports.lock().unwrap().push(report.clone());
+            })
+        };
+        (watchdog, reports)
+    }
+
+    // release 빌드에서 인라인되면 backtrace에 이 함수가 남지 않는다.
+    #[inline(never)]
+    fn hold_counting_permit(semaphore: &CountingSemaphore, hold: Duration) {
+        semaphore.acquire();
+        thread::sleep(hold);
+        semaphore.release();
+    }
+
+    // threshold를 넘겨 permit을 들고 있으면 스레드 이름, 세마포어 이름, acquire 위치의 backtrace와 함께 반복해서 보고해야 합니다.
+    #[test]
+    fn test_watchdog_reports_and_escalates_long_holds() {
+        let (watchdog, reports) = collecting_watchdog(20);
+        let semaphore = Arc::new(CountingSemaphore::new(2));
+        assert!(semaphore.watch(&watchdog, "db-writes"));
+        assert!(!semaphore.watch(&watchdog, "db-writes"), "A semaphore is watched only once");
+
+        {
+            let semaphore = Arc::clone(&semaphore);
+            thread::Builder::new()
+                .name("slow-holder".into())
+                .spawn(move || hold_counting_permit(&semaphore, Duration::from_millis(75)))
+                .unwrap()
+                .join()
+                .unwrap();
+        }
+        thread::sleep(Duration::from_millis(20));
+
+        let reports = reports.lock().unwrap();
+        let levels: Vec<u32> = reports.iter().map(|r| r.escalation).collect();
+        assert_eq!(levels, vec![1, 2, 3]);
+        assert!(reports.iter().all(|r| r.semaphore == "db-writes"));
+        assert!(reports.iter().all(|r| r.thread_name.as_deref() == Some("slow-holder")));
+        assert!(reports[0].held_for >= Duration::from_millis(20));
+        assert!(reports[0].backtrace.to_string().contains("hold_counting_permit"));
+    }
+
+    // 짧게 잡았다 놓는 permit은 보고되지 않아야 하며, 다른 세마포어 타입도 감시할 수 있어야 합니다.
+    #[test]
+    fn test_watchdog_ignores_short_holds_across_types() {
+        let (watchdog, reports) = collecting_watchdog(50);
+        let strong = StrongSemaphore::new(1);
+        let binary = BinarySemaphore::new();
+        strong.watch(&watchdog, "strong");
+        binary.watch(&watchdog, "binary");
+
+        for _ in 0..10 {
+            strong.acquire();
+            binary.acquire();
+            thread::sleep(Duration::from_millis(2));
+            binary.release();
+            strong.release();
+        }
+
+        binary.acquire();
+        thread::sleep(Duration::from_millis(70));
+        binary.release();
+
+        let names: Vec<String> = reports.lock().unwrap().iter().map(|r| r.semaphore.clone()).collect();
+        assert_eq!(names, vec!["binary".to_string()]);
+    }
+}
@@ -1,9 +1,9 @@
 #[cfg(test)]
 mod weak_semaphore_tests {
-    use std::sync::{Arc, Mutex};
+    use std::sync::{mpsc, Arc, Mutex};
     use std::thread;
     use std::time::{Duration};
-    use concurrency_project::semaphore::weak_semaphore::WeakSemaphore;
+    use concurrency_project::semaphore::weak_semaphore::{WakePolicy, WeakSemaphore};
 
     // 동시성 제어 테스트
     #[test]
@@ -88,9 +88,92 @@ mod weak_semaphore_tests {
         handle.join().unwrap();
         assert_eq!(*flag.lock().unwrap(), true, "Semaphore should have been acquired and released");
     }
+
+    fn release_to_waiters(policy: WakePolicy, waiters: usize) -> (Arc<WeakSemaphore>, Vec<usize>) {
+        let semaphore = Arc::new(WeakSemaphore::new(0).with_wake_policy(policy));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let handles: Vec<_> = (0..waiters)
+            .map(|i| {
+                let sem = Arc::clone(&semaphore);
+                let order = Arc::clone(&order);
+                thread::spawn(move || {
+                    sem.acquire();
+                    order.lock().unwrap().push(i);
+                })
+            })
+            .collect();
+        while semaphore.waiting_threads() < waiters {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        for _ in 0..waiters {
+            semaphore.release();
+            thread::sleep(Duration::from_millis(5));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let order = order.lock().unwrap().clone();
+        (semaphore, order)
+    }
+
+    // notify_all은 release마다 모든 대기자를 깨워 헛된 wakeup이 생기지만, notify_one과 random은 하나만 깨워야 합니다.
+    #[test]
+    fn test_weak_semaphore_wake_policies() {
+        let (all, _) = release_to_waiters(WakePolicy::NotifyAll, 10);
+        let (one, _) = release_to_waiters(WakePolicy::NotifyOne, 10);
+        let (random, order) = release_to_waiters(WakePolicy::Random, 10);
+        println!("NotifyAll futile wakeups: {}, spurious: {}", all.futile_wakeups(), all.spurious_wakeups());
+
+        assert!(all.futile_wakeups() > 0, "notify_all should wake threads that cannot get a permit");
+        assert_eq!(one.futile_wakeups(), 0);
+        assert_eq!(random.futile_wakeups(), 0);
+        let mut served = order.clone();
+        served.sort();
+        assert_eq!(served, (0..10).collect::<Vec<_>>(), "Every waiter should be served");
+        assert!(format!("{:?}", random).contains("wake_policy: Random"));
+    }
+
+    // release가 먼저 잠든 대기자의 타임아웃과 겹쳐도, permit은 그 대기자가 가져가거나 다음 대기자에게 넘어가야 합니다.
+    #[test]
+    fn test_weak_semaphore_timeout_does_not_lose_wakeup() {
+        for round in 0..40u64 {
+            let semaphore = Arc::new(WeakSemaphore::new(0));
+            let short = {
+                let sem = Arc::clone(&semaphore);
+                thread::spawn(move || {
+                    let result = sem.acquire_timeout(Duration::from_millis(10));
+                    if result.is_ok() {
+                        sem.release();
+                    }
+                    result
+                })
+            };
+            thread::sleep(Duration::from_millis(2));
+            let (tx, rx) = mpsc::channel();
+            let long = {
+                let sem = Arc::clone(&semaphore);
+                thread::spawn(move || {
+                    sem.acquire();
+                    tx.send(()).unwrap();
+                })
+            };
+
+            // short의 마감 시각 앞뒤로 release가 떨어지도록 조금씩 옮긴다.
+            thread::sleep(Duration::from_micros(6_000 + round % 8 * 500));
+            semaphore.release();
+            assert!(rx.recv_timeout(Duration::from_secs(2)).is_ok(), "wakeup lost in round {}", round);
+            let _ = short.join().unwrap();
+            long.join().unwrap();
+            assert_eq!(semaphore.available_permits(), 0);
+            assert_eq!(semaphore.waiting_threads(), 0);
+        }
+    }
 }
 
 // 동시성 제어: test_weak_semaphore_concurrency는 한 번에 하나의 스레드만 임계 영역에 접근할 수 있음을 확인합니다.
 // 공정성: test_weak_semaphore_fairness는 스레드 실행 순서가 약한 세마포어의 특성상 비결정적일 수 있음을 보여줍니다. 모든 스레드가 실행되었는지를 체크합니다.
 // 초기화 상태: test_weak_semaphore_zero_init는 세마포어가 0으로 초기화되었을 때의 동작을 확인합니다. 세마포어가 해제되기 전까지 스레드가 블록되어야 합니다.
-// 타이밍 검증: test_weak_semaphore_timing은 각 스레드가 세마포어를 획득하고 해제하는 시간을 기록하여 동작을 시각화합니다.
\ No newline at end of file
+// 타이밍 검증: test_weak_semaphore_timing은 각 스레드가 세마포어를 획득하고 해제하는 시간을 기록하여 동작을 시각화합니다.
+// wake 정책: test_weak_semaphore_wake_policies는 notify_all과 notify_one/random의 헛된 wakeup 수를 비교합니다.
+// 타임아웃 경합: test_weak_semaphore_timeout_does_not_lose_wakeup는 release와 대기자의 타임아웃이 겹쳐도 permit이 사라지지 않고 다음 대기자에게 넘어가는지 확인합니다.